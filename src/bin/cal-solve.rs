@@ -0,0 +1,355 @@
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+use calendar_puzzle_web::solver::*;
+use calendar_puzzle_web::render::{AnsiRenderer, GifRenderer, PngRenderer, SvgRenderer};
+use serde_json::json;
+
+const USAGE: &str = "\
+usage: cal-solve [options] <date>
+       cal-solve report [report options] <year>
+
+Solves the calendar puzzle for a date like `2026-10-17`, `Sat Oct 17` or `today` (in UTC), and prints the board.
+
+options:
+    --preset <name>    the edition of the puzzle: classic (the default), mirrored, ten-piece or month-day
+    --trace            print the board after every step the solver takes
+    --steps            print the number of steps the solver took
+    --all              print every distinct solution, instead of just the first one
+    --json             print one JSON object per line instead of boards
+    --color <when>     draw the pieces in color: auto (the default, when printing to a terminal), always or never
+    --svg <file>       also save the solution as an SVG image (with --all, the first solution)
+    --png <file>       also save the solution as a PNG image (with --all, the first solution)
+    --gif <file>       also save an animation of the solver finding the (first) solution as a GIF image
+    -h, --help         print this message
+
+The report solves every day of a year, and writes one row per day with its weekday, whether it is solvable, the
+number of steps to the first solution, the number of distinct solutions, and the share code of the first solution.
+Counting the solutions takes a few seconds per day, so a report takes a while; progress is printed to stderr.
+
+report options:
+    --preset <name>    as above
+    --format <format>  csv (the default) or jsonl
+    --output <file>    where to write the report, instead of stdout";
+
+struct Options {
+    preset: Preset,
+    target: TargetDate,
+    trace: bool,
+    steps: bool,
+    all: bool,
+    json: bool,
+    renderer: AnsiRenderer,
+    svg_output: Option<String>,
+    png_output: Option<String>,
+    gif_output: Option<String>,
+}
+
+struct ReportOptions {
+    preset: Preset,
+    year: i32,
+    format: ReportFormat,
+    output: Option<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ReportFormat {
+    Csv,
+    JsonLines,
+}
+
+pub fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("report") {
+        args.next();
+        let options = parse_report_args(args);
+        return match write_report(&options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("cal-solve: failed to write the report: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let options = parse_args(args);
+    let solved = if options.all {
+        print_all_solutions(&options)
+    } else {
+        print_first_solution(&options)
+    };
+
+    if solved {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn print_first_solution(options: &Options) -> bool {
+    let mut step = 0;
+    for msg in solve_preset(options.preset, options.target) {
+        match msg {
+            SolverMsg::Unsolved(_, mask) if options.trace => {
+                step += 1;
+                if options.json {
+                    println!("{}", json!({ "step": step, "mask": mask }));
+                } else {
+                    println!("step {}\n{}\n", step, options.renderer.render(&mask));
+                }
+            }
+            SolverMsg::Solved(solution) => {
+                save_images(options, &solution);
+                print_solution(options, &solution);
+                return true;
+            }
+            _ => {}
+        }
+    }
+
+    print_unsolvable(options);
+    false
+}
+
+fn print_all_solutions(options: &Options) -> bool {
+    let mut count = 0;
+    for solution in solve_all(options.preset, options.target) {
+        count += 1;
+        if count == 1 {
+            save_images(options, &solution);
+        }
+        if !options.json {
+            println!("solution #{}", count);
+        }
+        print_solution(options, &solution);
+    }
+
+    if count == 0 {
+        print_unsolvable(options);
+    } else if !options.json {
+        println!("{} solutions", count);
+    }
+
+    count > 0
+}
+
+fn print_solution(options: &Options, solution: &Solution) {
+    if options.json {
+        println!("{}", json!({ "target": options.target, "solution": solution }));
+    } else {
+        println!("{}", options.renderer.render(&solution.mask));
+        if options.steps {
+            println!("solved in {} steps", solution.steps);
+        }
+        println!();
+    }
+}
+
+fn save_images(options: &Options, solution: &Solution) {
+    if let Some(path) = &options.svg_output {
+        save_image(path, SvgRenderer::new(options.preset).render(&solution.mask).as_bytes());
+    }
+    if let Some(path) = &options.png_output {
+        save_image(path, &PngRenderer::new(options.preset).render(&solution.mask));
+    }
+    if let Some(path) = &options.gif_output {
+        // the animation needs every step again, which is quicker than keeping them all around while solving
+        let gif = GifRenderer::new(options.preset).render(solve_preset(options.preset, options.target));
+        save_image(path, &gif.expect("the solver found a solution"));
+    }
+}
+
+fn save_image(path: &str, contents: &[u8]) {
+    if let Err(err) = std::fs::write(path, contents) {
+        eprintln!("cal-solve: failed to write {}: {}", path, err);
+    }
+}
+
+fn print_unsolvable(options: &Options) {
+    if options.json {
+        println!("{}", json!({ "target": options.target, "solution": null }));
+    } else {
+        println!("{} has no solution on the {} board", Localized::new(options.target, Locale::English), options.preset);
+    }
+}
+
+fn write_report(options: &ReportOptions) -> io::Result<()> {
+    let mut out: BufWriter<Box<dyn Write>> = BufWriter::new(match &options.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    });
+
+    if options.format == ReportFormat::Csv {
+        writeln!(out, "date,weekday,solvable,steps,solutions,share_code")?;
+    }
+
+    let first_day = TargetDate::from_ymd(options.year, Month::January, 1).expect("every year has a January 1st");
+    for target in TargetDateIter::create(first_day, is_leap_year(options.year)) {
+        let date = target.in_year(options.year).expect("the iterated dates are in the year");
+        let mut solutions = solve_all(options.preset, target);
+        let first_solution = solutions.next();
+        let num_solutions = first_solution.map(|_| 1 + solutions.count()).unwrap_or(0);
+        let steps = first_solution.map(|solution| solution.steps);
+        let share_code = first_solution.map(|solution| solution.shared(target).to_string());
+
+        match options.format {
+            ReportFormat::Csv => writeln!(out, "{},{},{},{},{},{}",
+                date,
+                target.day_of_week,
+                first_solution.is_some(),
+                steps.map(|steps| steps.to_string()).unwrap_or_default(),
+                num_solutions,
+                share_code.unwrap_or_default())?,
+            ReportFormat::JsonLines => writeln!(out, "{}", json!({
+                "date": date.to_string(),
+                "weekday": target.day_of_week,
+                "solvable": first_solution.is_some(),
+                "steps": steps,
+                "solutions": num_solutions,
+                "share_code": share_code,
+            }))?,
+        }
+
+        eprintln!("{}: {} solutions", date, num_solutions);
+    }
+
+    out.flush()
+}
+
+fn parse_report_args(mut args: impl Iterator<Item=String>) -> ReportOptions {
+    let mut preset = Preset::default();
+    let mut format = ReportFormat::Csv;
+    let mut output = None;
+    let mut year = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--preset" => preset = parse_preset_arg(args.next()),
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("csv") => ReportFormat::Csv,
+                    Some("jsonl") => ReportFormat::JsonLines,
+                    Some(other) => fail(&format!("unknown report format {:?}", other)),
+                    None => fail("--format needs a format"),
+                }
+            }
+            "--output" => output = Some(args.next().unwrap_or_else(|| fail("--output needs a file"))),
+            flag if flag.starts_with("--") => fail(&format!("unknown option {}", flag)),
+            _ if year.is_some() => fail("more than one year given"),
+            _ => year = Some(arg.parse().unwrap_or_else(|_| fail(&format!("{:?} is not a year", arg)))),
+        }
+    }
+
+    let year = year.unwrap_or_else(|| fail("no year given"));
+    ReportOptions { preset, year, format, output }
+}
+
+fn parse_args(mut args: impl Iterator<Item=String>) -> Options {
+    let mut preset = Preset::default();
+    let mut trace = false;
+    let mut steps = false;
+    let mut all = false;
+    let mut json = false;
+    let mut color = None;
+    let mut svg_output = None;
+    let mut png_output = None;
+    let mut gif_output = None;
+    let mut date_words = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--preset" => preset = parse_preset_arg(args.next()),
+            "--trace" => trace = true,
+            "--steps" => steps = true,
+            "--all" => all = true,
+            "--json" => json = true,
+            "--color" => {
+                color = match args.next().as_deref() {
+                    Some("auto") => None,
+                    Some("always") => Some(true),
+                    Some("never") => Some(false),
+                    Some(other) => fail(&format!("--color must be auto, always or never, not {:?}", other)),
+                    None => fail("--color needs a value"),
+                }
+            }
+            "--svg" => svg_output = Some(args.next().unwrap_or_else(|| fail("--svg needs a file"))),
+            "--png" => png_output = Some(args.next().unwrap_or_else(|| fail("--png needs a file"))),
+            "--gif" => gif_output = Some(args.next().unwrap_or_else(|| fail("--gif needs a file"))),
+            flag if flag.starts_with("--") => fail(&format!("unknown option {}", flag)),
+            // dates like `Sat Oct 17` may be passed without quotes
+            _ => date_words.push(arg),
+        }
+    }
+
+    if trace && all {
+        fail("--trace cannot be combined with --all");
+    }
+
+    let target = parse_target(&date_words.join(" "), preset);
+    let renderer = AnsiRenderer {
+        // see https://no-color.org
+        color: color.unwrap_or_else(|| io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()),
+        ..AnsiRenderer::new(preset)
+    };
+    Options { preset, target, trace, steps, all, json, renderer, svg_output, png_output, gif_output }
+}
+
+fn parse_target(date: &str, preset: Preset) -> TargetDate {
+    if date.trim().is_empty() {
+        fail("no date given");
+    }
+
+    if date.trim().eq_ignore_ascii_case("today") {
+        return today().target();
+    }
+
+    match date.parse() {
+        Ok(target) => target,
+        // the solver ignores the weekday on boards which don't show it, so any will do
+        Err(ParseDateError::MissingWeekday) if !preset.semantics().uses_weekday() => {
+            parse_target(&format!("{} Monday", date), preset)
+        }
+        Err(err) => fail_without_usage(&err.to_string()),
+    }
+}
+
+fn parse_preset_arg(name: Option<String>) -> Preset {
+    let name = name.unwrap_or_else(|| fail("--preset needs a name"));
+    Preset::ALL.into_iter()
+        .find(|preset| preset_slug(*preset).eq_ignore_ascii_case(&name))
+        .unwrap_or_else(|| fail(&format!("there is no preset named {:?}", name)))
+}
+
+fn preset_slug(preset: Preset) -> &'static str {
+    match preset {
+        Preset::Classic => "classic",
+        Preset::Mirrored => "mirrored",
+        Preset::TenPiece => "ten-piece",
+        Preset::MonthDay => "month-day",
+    }
+}
+
+fn today() -> CalendarDate {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).expect("the clock is set to a date after 1970");
+    CalendarDate::from_days_since_epoch((since_epoch.as_secs() / (24 * 60 * 60)) as i64).expect("the clock is set to a year which fits in an i32")
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("cal-solve: {}\n\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
+// for mistakes in the arguments' values, where the options are used correctly
+fn fail_without_usage(message: &str) -> ! {
+    eprintln!("cal-solve: {}", message);
+    std::process::exit(2);
+}
@@ -0,0 +1,18 @@
+use std::io::Write;
+use calendar_puzzle_web::solver::generate_table;
+
+const DEFAULT_OUTPUT: &str = "src/solver/solutions.bin";
+
+pub fn main() {
+    let output = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_OUTPUT.to_string());
+    let mut stderr = std::io::stderr();
+    let table = generate_table(|target, solution| {
+        let _ = match solution {
+            Some(solution) => writeln!(stderr, "{:?} solved in {} steps", target, solution.steps),
+            None => writeln!(stderr, "{:?} has no solution", target),
+        };
+    });
+
+    std::fs::write(&output, table).unwrap_or_else(|err| panic!("failed to write {}: {}", output, err));
+    eprintln!("wrote table to {}", output);
+}
@@ -1,11 +1,27 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
 pub mod solver;
+pub mod render;
 pub mod macros;
+#[cfg(feature = "web")]
 pub mod web;
 
 #[cfg(test)]
 mod tests {
     use crate::solver::*;
 
+    const OCT_17: TargetDate = TargetDate{month: Month::October, day_of_month: 17, day_of_week: Weekday::Saturday};
+
+    fn solve_first(target: TargetDate) -> Solution {
+        match solve(target).last() {
+            Some(SolverMsg::Solved(solution)) => solution,
+            other => panic!("failed to solve {:?}: {:?}", target, other),
+        }
+    }
+
     #[test]
     fn test_solve() {
         for step in solve(TargetDate{month: Month::January, day_of_month: 19, day_of_week: Weekday::Friday})
@@ -13,4 +29,740 @@ mod tests {
             println!("{}", step);
         }
     }
+
+    #[test]
+    fn test_presets() {
+        for preset in Preset::ALL {
+            assert!(preset.is_area_consistent(), "{} pieces do not fit the board", preset);
+            let target = OCT_17;
+            assert!(matches!(solve_preset(preset, target).last(), Some(SolverMsg::Solved(_))), "{} failed to solve {:?}", preset, target);
+        }
+    }
+
+    #[test]
+    fn test_canonical_solution() {
+        // the domino of the ten piece edition looks the same when rotated by a half turn
+        let domino = Preset::TenPiece.num_pieces() - 1;
+        let mut a = GameState::new(Preset::TenPiece);
+        let mut b = GameState::new(Preset::TenPiece);
+        assert!(a.place_piece(domino, Some(Placement{x: 2, y: 3, rotation: 0, flipped: false}), BoardMask::filled()));
+        assert!(b.place_piece(domino, Some(Placement{x: 2, y: 3, rotation: 2, flipped: true}), BoardMask::filled()));
+        assert!(a != b);
+        assert_eq!(a.canonical(), b.canonical());
+
+        let mut c = a;
+        assert!(c.place_piece(domino, Some(Placement{x: 2, y: 3, rotation: 1, flipped: false}), BoardMask::filled()));
+        assert_ne!(a.canonical(), c.canonical());
+    }
+
+    #[test]
+    fn test_calendar_report() {
+        let analysis = |day_of_month, solutions, first_solution_steps| DateAnalysis {
+            target: TargetDate{month: Month::March, day_of_month, day_of_week: Weekday::Monday},
+            solutions,
+            first_solution_steps,
+        };
+        let report: CalendarReport = [
+            analysis(1, 4, Some(300)),
+            analysis(2, 1, Some(20)),
+            analysis(3, 0, None),
+            analysis(4, 9, Some(5000)),
+        ].into_iter().collect();
+
+        let days = |dates: Vec<DateAnalysis>| dates.iter().map(|a| a.target.day_of_month).collect::<Vec<_>>();
+        assert_eq!(days(report.hardest(2)), vec![4, 1]);
+        assert_eq!(days(report.easiest(5)), vec![2, 1, 4]);
+        assert_eq!(days(report.unique().collect()), vec![2]);
+        assert_eq!(days(report.unsolvable().collect()), vec![3]);
+    }
+
+    #[test]
+    #[cfg(feature = "precomputed")]
+    fn test_lookup_solution() {
+        let target = TargetDate{month: Month::January, day_of_month: 19, day_of_week: Weekday::Friday};
+        let solved = solve_first(target);
+        let looked_up = lookup_solution(target).expect("missing precomputed solution");
+        assert!(looked_up.game == solved.game);
+        assert_eq!(looked_up.steps, solved.steps);
+
+        assert!(lookup_solution(TargetDate{month: Month::February, day_of_month: 30, day_of_week: Weekday::Monday}).is_none());
+    }
+
+    // the table holds a solution for every date of the classic preset, and the other presets can be solved from those:
+    // the mirrored board by mirroring them, the ten piece edition by cutting the largest piece in two where it lies,
+    // and the month & day edition by covering the weekday with the extra square. So this is a quick check that every
+    // date of every preset can be solved.
+    #[test]
+    #[cfg(feature = "precomputed")]
+    fn test_every_preset_solves_every_date() {
+        for preset in Preset::ALL {
+            for target in preset.valid_dates() {
+                let classic = lookup_solution(target).unwrap_or_else(|| panic!("{} cannot solve {:?}", TABLE_PRESET, target)).mask;
+                let mut mask = classic;
+                match preset {
+                    Preset::Classic => {}
+                    Preset::Mirrored => for (x, y) in iter_coordinates() {
+                        mask.set(x, y, classic.get(PUZZLE_WIDTH - 1 - x, y));
+                    },
+                    Preset::TenPiece => {
+                        let largest = iter_coordinates().filter(|(x, y)| classic.get(*x, *y) == CellTag::Covered(8)).collect::<Vec<_>>();
+                        mask = Placement::iter_all()
+                            .filter_map(|placement| preset.piece_mask(9, &placement))
+                            .filter(|domino| iter_coordinates().all(|(x, y)| !domino.is_covered(x, y) || largest.contains(&(x, y))))
+                            .map(|domino| {
+                                let mut cut = classic;
+                                largest.iter().filter(|(x, y)| domino.is_covered(*x, *y)).for_each(|(x, y)| cut.set(*x, *y, CellTag::Covered(9)));
+                                cut
+                            })
+                            .find(|cut| GameState::from_tagged_mask(preset, cut).is_ok())
+                            .unwrap_or_else(|| panic!("cannot cut the largest piece in two for {:?}", target));
+                    }
+                    Preset::MonthDay => for (x, y) in iter_coordinates() {
+                        if let BoardLabel::DayOfWeekLabel(_) = preset.labels()[y][x] {
+                            if classic.get(x, y) == CellTag::Winner {
+                                mask.set(x, y, CellTag::Covered(9));
+                            }
+                        }
+                    },
+                }
+
+                let game = GameState::from_tagged_mask(preset, &mask).unwrap_or_else(|err| panic!("{} cannot solve {:?}: {}", preset, target, err));
+                assert!(game.placements().iter().all(Option::is_some), "{} leaves pieces out for {:?}", preset, target);
+                assert_eq!(Some(game.mask()), preset.winning_mask(target), "{} cannot solve {:?}", preset, target);
+            }
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_pieces() {
+        let solution = solve_first(OCT_17);
+        assert_eq!(NUM_PIECES, Preset::Classic.num_pieces());
+        assert_eq!(solution.game.pieces()[..], solution.game.placements()[..]);
+    }
+
+    #[test]
+    fn test_share_code() {
+        let target = OCT_17;
+        let winning_mask = Preset::TenPiece.winning_mask(target).unwrap();
+        let mut game = GameState::new(Preset::TenPiece);
+        assert!(game.place_piece(0, Some(Placement{x: 0, y: 2, rotation: 0, flipped: false}), winning_mask));
+        assert!(game.place_piece(9, Some(Placement{x: 0, y: 8, rotation: 0, flipped: false}), winning_mask));
+
+        let shared = SharedSolution::new(target, game);
+        let code = shared.to_string();
+        assert_eq!(code.len(), 18);
+        assert_eq!(code.parse::<SharedSolution>(), Ok(shared));
+        assert!(!shared.is_solved());
+
+        assert_eq!("".parse::<SharedSolution>(), Err(ShareCodeError::TooShort));
+        assert_eq!(code[..10].parse::<SharedSolution>(), Err(ShareCodeError::TooShort));
+        assert_eq!(format!("{}A", code).parse::<SharedSolution>(), Err(ShareCodeError::TooLong));
+        assert_eq!(code.replacen('A', "!", 1).parse::<SharedSolution>(), Err(ShareCodeError::InvalidCharacter('!')));
+        // 105 bits fill 18 characters with 3 bits to spare, which are always zero
+        let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        let last = alphabet.find(&code[17..]).unwrap();
+        let padded = format!("{}{}", &code[..17], &alphabet[last + 1..last + 2]);
+        assert_eq!(padded.parse::<SharedSolution>(), Err(ShareCodeError::InvalidPadding));
+
+        // splice in the code of a domino placed on top of the first piece, which place_piece would refuse
+        let mut domino_only = GameState::new(Preset::TenPiece);
+        assert!(domino_only.place_piece(9, Some(Placement{x: 0, y: 2, rotation: 0, flipped: false}), winning_mask));
+        let conflicting = format!("{}{}", &code[..15], &SharedSolution::new(target, domino_only).to_string()[15..]);
+        assert_eq!(conflicting.parse::<SharedSolution>(), Err(ShareCodeError::Conflict(9)));
+    }
+
+    #[test]
+    fn test_piece_geometry() {
+        // the P pentomino has no symmetry, the domino looks the same turned over or upside down, and the square always
+        let p_pentomino = Preset::Classic.piece(7).unwrap();
+        assert_eq!(p_pentomino.area(), 5);
+        assert_eq!(p_pentomino.symmetries().len(), 1);
+        assert_eq!(p_pentomino.orientations().len(), 8);
+        let domino = Preset::TenPiece.piece(9).unwrap();
+        assert_eq!(domino.symmetries().len(), 4);
+        assert_eq!(domino.orientations(), vec![Orientation::default(), Orientation { rotation: 1, flipped: false }]);
+        assert_eq!(Preset::MonthDay.piece(9).unwrap().orientations().len(), 1);
+        assert!(Preset::Classic.piece(9).is_none());
+
+        assert_eq!(p_pentomino.cells(Orientation::default()).collect::<Vec<_>>(), vec![(1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+        assert_eq!(p_pentomino.cells(Orientation { rotation: 1, flipped: false }).collect::<Vec<_>>(), vec![(0, 0), (1, 0), (2, 0), (1, 1), (2, 1)]);
+        for orientation in Orientation::ALL {
+            assert_eq!(p_pentomino.cells(orientation).count(), 5);
+        }
+
+        // the same shape drawn upside down is normalized to the same cells
+        let l_tetromino = Piece::with_mask([[true, false], [true, false], [true, true]]).unwrap();
+        assert_eq!(l_tetromino.normalized_cells(), Preset::TenPiece.piece(8).unwrap().normalized_cells());
+        assert_ne!(Preset::Classic.piece(6).unwrap().normalized_cells(), Preset::Classic.piece(4).unwrap().normalized_cells());
+
+        let placement = Placement { x: 4, y: 6, rotation: 1, flipped: false };
+        assert_eq!(placement.orientation().at(4, 6), placement);
+        assert_eq!(p_pentomino.bounding_box(&placement), None);
+        let rect = p_pentomino.bounding_box(&Placement { x: 3, ..placement }).unwrap();
+        assert_eq!(rect, Rect { x: 3, y: 6, width: 3, height: 2 });
+        assert!(rect.contains(5, 7) && !rect.contains(3, 8));
+    }
+
+    #[test]
+    fn test_shape() {
+        let shape: Shape = "
+            ##.
+            .#.
+            ###
+        ".parse().unwrap();
+        assert_eq!((shape.width(), shape.height(), shape.area()), (3, 3, 6));
+        assert_eq!(shape.to_string(), "##.\n.#.\n###");
+        assert_eq!(Shape::try_from([[true, true, false], [false, true, false], [true, true, true]]), Ok(shape.clone()));
+        assert_eq!(Preset::Classic.piece(0).unwrap().shape(), shape);
+
+        // a quarter turn counter-clockwise, and turning over, match the placements of the pieces
+        assert_eq!(shape.rotated().to_string(), "..#\n###\n#.#");
+        assert_eq!(shape.reflected().to_string(), ".##\n.#.\n###");
+        assert_eq!(shape.rotated().rotated().rotated().rotated(), shape);
+        for orientation in Orientation::ALL {
+            let oriented = shape.oriented(orientation);
+            assert_eq!(oriented.cells(), Preset::Classic.piece(0).unwrap().cells(orientation).collect::<Vec<_>>());
+            assert!(oriented.is_congruent(&shape));
+            assert_eq!(oriented.canonical(), shape.canonical());
+        }
+        assert!(!shape.is_congruent(&"###\n#.#".parse().unwrap()));
+
+        assert_eq!(shape.translated(2, 1).next(), Some((2, 1)));
+        assert!(shape.mask_at(3, 6).is_some());
+        assert!(shape.mask_at(4, 6).is_none());
+
+        let square: Shape = "##\n##".parse().unwrap();
+        assert_eq!(square.symmetries().len(), 8);
+        assert_eq!(square.orientations(), vec![Orientation::default()]);
+        assert_eq!(shape.symmetries(), vec![Orientation::default()]);
+
+        assert_eq!("..\n".parse::<Shape>(), Err(ParseShapeError::Empty));
+        assert_eq!("#x".parse::<Shape>(), Err(ParseShapeError::InvalidCharacter('x')));
+    }
+
+    #[test]
+    fn test_invalid_shape() {
+        assert_eq!(Shape::try_from([[false; 3]; 2]), Err(ShapeError::Empty));
+        assert_eq!(Shape::new([]), Err(ShapeError::Empty));
+        assert_eq!(Piece::with_mask([[false; 3]; 2]).err(), Some(ShapeError::Empty));
+
+        // a bar as long as the board is wide fits, one cell longer only fits standing up
+        assert!(Piece::with_mask([[true; PUZZLE_WIDTH]]).is_ok());
+        let too_wide: Shape = "#######".parse().unwrap();
+        assert_eq!(Piece::new(&too_wide).err(), Some(ShapeError::TooLarge { width: 7, height: 1 }));
+        assert!(Piece::new(&too_wide.rotated()).is_ok());
+    }
+
+    #[test]
+    fn test_solve_pieces() {
+        // pieces made at runtime, which cover a block of 4x2 cells in the corner of the board
+        let pieces = ["#..\n###", "###\n..#"].map(|text| Piece::new(&text.parse().unwrap()).unwrap());
+        let block = format!("****..\n****..\n{}", "......\n".repeat(PUZZLE_HEIGHT - 2)).parse::<BoardMask>().unwrap();
+        let solution = match solve_pieces(&pieces, block).last() {
+            Some(PieceSolverMsg::Solved(solution)) => solution,
+            other => panic!("failed to cover the block: {:?}", other),
+        };
+        assert_eq!(solution.placements.len(), 2);
+        assert_eq!(BoardMask::from(solution.mask), block);
+        assert_eq!(solve_pieces(&pieces[..1], block).last(), Some(PieceSolverMsg::Impossible));
+        assert_eq!(solve_pieces(&[pieces[0]; MAX_PIECES + 1], block).collect::<Vec<_>>(), vec![PieceSolverMsg::Impossible]);
+
+        // with the pieces of a preset, the board is covered the same way as for the preset
+        let classic = Preset::Classic.pieces().to_vec();
+        match solve_pieces(&classic, Preset::Classic.winning_mask(OCT_17).unwrap()).last() {
+            Some(PieceSolverMsg::Solved(solution)) => assert!(solution.mask == solve_first(OCT_17).mask),
+            other => panic!("failed to solve {:?}: {:?}", OCT_17, other),
+        }
+    }
+
+    #[test]
+    fn test_solve_from() {
+        let target = OCT_17;
+        let solved = |msg| match msg {
+            SolverMsg::Solved(solution) => Some(solution),
+            _ => None,
+        };
+        let previous = solve(target).find_map(solved).unwrap();
+
+        // nothing needs to move for the same date
+        let same = solve_from(&previous, target).find_map(solved).unwrap();
+        assert_eq!((same.steps, same.moved_pieces(&previous)), (0, 0));
+
+        let next_day = target.next(false).unwrap();
+        let solution = solve_from(&previous, next_day).find_map(solved).unwrap();
+        assert_eq!(BoardMask::from(solution.mask), Preset::Classic.winning_mask(next_day).unwrap());
+        assert!(solution.moved_pieces(&previous) > 0);
+        assert!(solve_from(&previous, TargetDate{day_of_month: 32, ..target}).next().is_none());
+
+        // when only the day moves, by one cell, the pieces around it make room and the others stay
+        let next_cell = TargetDate{day_of_month: 18, ..target};
+        let warm = solve_from(&previous, next_cell).find_map(solved).unwrap();
+        let cold = solve(next_cell).find_map(solved).unwrap();
+        assert!(warm.moved_pieces(&previous) <= 2, "moved {} pieces", warm.moved_pieces(&previous));
+        assert!(warm.steps < cold.steps / 10, "took {} steps, {} from an empty board", warm.steps, cold.steps);
+    }
+
+    #[test]
+    #[cfg(all(feature = "web", feature = "precomputed"))]
+    fn test_web_warm_start() {
+        use crate::web::{solver_start, SolverStart};
+
+        let target = TargetDate{month: Month::October, day_of_month: 16, day_of_week: Weekday::Friday};
+        let previous = match solver_start(Preset::Classic, target, None) {
+            SolverStart::Precomputed(solution) => solution,
+            _ => panic!("missing precomputed solution for {:?}", target),
+        };
+
+        // stepping to the next day solves it from the last solution, instead of looking it up
+        let next_day = target.next(true).unwrap();
+        assert!(matches!(solver_start(Preset::Classic, next_day, Some((target, &previous))), SolverStart::Warm(solution) if solution == previous));
+
+        // any other date is looked up, even with a solution to start from
+        assert!(matches!(solver_start(Preset::Classic, OCT_17, Some((OCT_17, &previous))), SolverStart::Precomputed(_)));
+        assert!(matches!(solver_start(Preset::Classic, target, Some((next_day, &previous))), SolverStart::Precomputed(_)));
+        let a_week_later = TargetDate{day_of_month: 23, ..target};
+        assert!(matches!(solver_start(Preset::Classic, a_week_later, Some((target, &previous))), SolverStart::Precomputed(_)));
+
+        // the other presets have no table, and the last solution is only a start for the same preset
+        assert!(matches!(solver_start(Preset::Mirrored, next_day, Some((target, &previous))), SolverStart::Cold));
+        let mirrored = solve_preset(Preset::Mirrored, target).find_map(|msg| match msg {
+            SolverMsg::Solved(solution) => Some(solution),
+            _ => None,
+        }).unwrap();
+        assert!(matches!(solver_start(Preset::Mirrored, next_day, Some((target, &mirrored))), SolverStart::Warm(_)));
+        assert!(matches!(solver_start(Preset::Mirrored, a_week_later, Some((target, &mirrored))), SolverStart::Cold));
+
+        // the end of February steps to the 29th, or past it
+        let february_28 = TargetDate{month: Month::February, day_of_month: 28, day_of_week: Weekday::Saturday};
+        let last = match solver_start(Preset::Classic, february_28, None) {
+            SolverStart::Precomputed(solution) => solution,
+            _ => panic!("missing precomputed solution for {:?}", february_28),
+        };
+        for next_day in [true, false].map(|leap_year| february_28.next(leap_year).unwrap()) {
+            assert!(matches!(solver_start(Preset::Classic, next_day, Some((february_28, &last))), SolverStart::Warm(_)));
+        }
+    }
+
+    #[test]
+    fn test_solve_memoized() {
+        let solved = |msg| match msg {
+            SolverMsg::Solved(solution) => Some(solution),
+            _ => None,
+        };
+        let target = TargetDate{month: Month::January, day_of_month: 8, day_of_week: Weekday::Thursday};
+        let plain = solve(target).find_map(solved).unwrap();
+        assert!(plain.memo().is_none());
+
+        let memoized = solve_memoized(Preset::Classic, target, 1 << 16).find_map(solved).unwrap();
+        let memo = memoized.memo().unwrap();
+        assert_eq!(BoardMask::from(memoized.mask), Preset::Classic.winning_mask(target).unwrap());
+        assert!(memoized.steps <= plain.steps);
+        assert!(memo.hits > 0 && memo.hits <= memo.lookups);
+        assert!(Solution { memo: None, ..memoized }.memo().is_none());
+
+        // with a single slot, almost every dead board evicts the one before it
+        let tiny = solve_memoized(Preset::Classic, target, 1).find_map(solved).unwrap();
+        assert!(tiny.steps <= plain.steps);
+        let tiny_memo = tiny.memo().unwrap();
+        assert_eq!(tiny_memo.evictions + 1, tiny_memo.insertions);
+    }
+
+    #[test]
+    fn test_target_pattern() {
+        let target = OCT_17;
+        let exact = TargetPattern::from(target);
+        assert_eq!(exact.candidates(Preset::Classic).collect::<Vec<_>>(), vec![target]);
+        assert!(exact.matches(target) && !exact.matches(TargetDate{day_of_week: Weekday::Sunday, ..target}));
+
+        // a single date is searched the same way as by solve_preset
+        match solve_pattern(Preset::Classic, exact).last() {
+            Some(SolverMsg::Solved(solution)) => assert_eq!(solution.mask, solve_first(target).mask),
+            other => panic!("failed to solve {:?}: {:?}", exact, other),
+        }
+
+        // the weekday is left to the solver, except on the month & day board where it doesn't matter anyway
+        let any_weekday = exact.with_any_weekday();
+        assert_eq!(any_weekday.candidates(Preset::Classic).count(), 7);
+        assert_eq!(any_weekday.candidates(Preset::MonthDay).count(), 1);
+        let solution = match solve_pattern(Preset::Classic, any_weekday).last() {
+            Some(SolverMsg::Solved(solution)) => solution,
+            other => panic!("failed to solve {:?}: {:?}", any_weekday, other),
+        };
+        let solved = any_weekday.solved_target(&solution).unwrap();
+        assert_eq!((solved.month, solved.day_of_month), (Month::October, 17));
+        assert_eq!(Preset::Classic.winning_mask(solved), Some(solution.game.mask()));
+        assert_eq!(solution.mask.shown_target(Preset::Classic), Ok(solved));
+
+        // on the month & day board, the date has the first weekday of the pattern
+        let weekend = any_weekday.with_weekdays([Weekday::Saturday, Weekday::Sunday]);
+        let on_saturday = match solve_pattern(Preset::MonthDay, weekend).last() {
+            Some(SolverMsg::Solved(solution)) => weekend.solved_target(&solution),
+            other => panic!("failed to solve {:?}: {:?}", weekend, other),
+        };
+        assert_eq!(on_saturday, Some(target));
+
+        // without the domino, the ten piece set leaves two more cells open, which can be any of the blank cells
+        let blank_cells = iter_coordinates()
+            .filter(|(x, y)| BOARD_LABELS[*y][*x] == BoardLabel::Unlabeled)
+            .fold(BoardMask::EMPTY, |mut mask, (x, y)| {
+                mask.set_covered(x, y, true);
+                mask
+            });
+        let with_blanks = any_weekday.with_free_cells(blank_cells);
+        let pieces = &Preset::TenPiece.pieces()[..9];
+        let solution = match solve_pattern_pieces(Preset::Classic, pieces, with_blanks).last() {
+            Some(PieceSolverMsg::Solved(solution)) => solution,
+            other => panic!("failed to solve {:?}: {:?}", with_blanks, other),
+        };
+        let open = BoardMask::from(solution.mask);
+        assert_eq!(iter_coordinates().filter(|(x, y)| !open.is_covered(*x, *y) && blank_cells.is_covered(*x, *y)).count(), 2);
+        assert_eq!(with_blanks.shown_target(Preset::Classic, open).map(|shown| (shown.month, shown.day_of_month)), Some((Month::October, 17)));
+        assert_eq!(solve_pattern_pieces(Preset::Classic, pieces, any_weekday).last(), Some(PieceSolverMsg::Impossible));
+        assert_eq!(solve_pattern_pieces(Preset::Classic, pieces, any_weekday).last(), Some(PieceSolverMsg::Impossible));
+
+        let fridays_in_february = TargetPattern::ANY.with_months([Month::February]).with_weekdays([Weekday::Friday]);
+        assert_eq!(fridays_in_february.candidates(Preset::Classic).count(), 29);
+        assert_eq!(TargetPattern::ANY.with_days([0, 32]).candidates(Preset::Classic).count(), 0);
+        assert!(solve_pattern(Preset::Classic, exact.with_months([Month::February]).with_days([30])).next().is_none());
+    }
+
+    #[test]
+    fn test_shown_target() {
+        for preset in Preset::ALL {
+            for target in preset.valid_dates().step_by(53) {
+                let winning_mask = preset.winning_mask(target).unwrap();
+                let shown = preset.shown_target(winning_mask).unwrap();
+                assert_eq!((shown.month, shown.day_of_month), (target.month, target.day_of_month));
+                assert_eq!(preset.winning_mask(shown), Some(winning_mask));
+            }
+        }
+
+        let target = OCT_17;
+        let mut mask = Preset::Classic.winning_mask(target).unwrap();
+        mask.set_covered(0, 0, false);
+        assert_eq!(Preset::Classic.shown_target(mask), Err(ShownDateError::MultipleMonths(Month::January, Month::October)));
+        mask.set_covered(0, 0, true);
+        mask.set_covered(1, 7, false);
+        assert_eq!(Preset::Classic.shown_target(mask), Err(ShownDateError::ExtraCell { x: 1, y: 7 }));
+        assert_eq!(Preset::Classic.shown_target(BoardMask::filled()), Err(ShownDateError::MissingMonth));
+        // the month & day board has no weekday to show
+        assert_eq!(Preset::MonthDay.shown_target(Preset::Classic.winning_mask(target).unwrap()), Err(ShownDateError::ExtraCell { x: 4, y: 8 }));
+        let february_30 = TargetDate{month: Month::February, day_of_month: 30, ..target};
+        let mask = Preset::Classic.winning_mask(february_30).unwrap();
+        assert_eq!(Preset::Classic.shown_target(mask), Err(ShownDateError::InvalidDay { month: Month::February, day: 30 }));
+
+        // a half finished game shows too much
+        let mut game = GameState::new(Preset::Classic);
+        assert!(game.place_piece(0, Some(Placement{x: 0, y: 0, rotation: 0, flipped: false}), BoardMask::filled()));
+        assert_eq!(game.shown_target(), Err(ShownDateError::MultipleMonths(Month::March, Month::April)));
+        let solution = solve_first(target);
+        assert_eq!(solution.game.shown_target(), Ok(target));
+        assert_eq!(solution.mask.shown_target(Preset::Classic), Ok(target));
+    }
+
+    #[test]
+    fn test_parse_mask() {
+        let target = OCT_17;
+        let solution = solve_first(target);
+        let text = solution.mask.to_string();
+        assert_eq!(text.parse::<TaggedMask>(), Ok(solution.mask));
+        assert_eq!(text.parse::<BoardMask>(), Err(ParseMaskError::InvalidCell { row: 0, column: 0, found: text.chars().nth(1).unwrap() }));
+        let board_mask = BoardMask::from(solution.mask);
+        assert_eq!(board_mask.to_string().parse::<BoardMask>(), Ok(board_mask));
+
+        // the compact format, with surrounding empty lines
+        let compact = text.lines().map(|line| line.replace(['[', ']'], "").replace(' ', ".")).collect::<Vec<_>>().join("\n");
+        assert_eq!(format!("\n{}\n\n", compact).parse::<TaggedMask>(), Ok(solution.mask));
+
+        // spaces at the start and end of a compact row are cells too
+        let mut sparse = TaggedMask::default();
+        sparse.set(2, 0, CellTag::Covered(1));
+        assert_eq!(format!("  1   \n{}", "      \n".repeat(PUZZLE_HEIGHT - 1)).parse::<TaggedMask>(), Ok(sparse));
+
+        // indented, with spaces after the rows, like a board pasted into a string in source code
+        let indent = |text: &str| format!("\n{}\n    ", text.lines().map(|line| format!("    {}  ", line)).collect::<Vec<_>>().join("\n"));
+        assert_eq!(indent(&text).parse::<TaggedMask>(), Ok(solution.mask));
+        assert_eq!(indent(&compact).parse::<TaggedMask>(), Ok(solution.mask));
+        assert_eq!(indent(&board_mask.to_string()).parse::<BoardMask>(), Ok(board_mask));
+
+        let game = GameState::from_tagged_mask(Preset::Classic, &solution.mask).unwrap();
+        assert_eq!(game.canonical(), solution.canonical());
+        assert_eq!(game.tagged_mask(Preset::Classic.winning_mask(target).unwrap()), solution.mask);
+
+        let errors = [
+            ("......", ParseMaskError::WrongRowCount(1)),
+            (&compact[1..], ParseMaskError::WrongCellCount { row: 0, cells: 5 }),
+            (&format!("x{}", &compact[1..]), ParseMaskError::InvalidCell { row: 0, column: 0, found: 'x' }),
+            (&text.replacen("][", "]x[", 1), ParseMaskError::InvalidCell { row: 0, column: 1, found: 'x' }),
+        ];
+        for (text, error) in errors {
+            assert_eq!(text.parse::<TaggedMask>(), Err(error));
+        }
+
+        // the classic preset has 9 pieces, and its shapes differ from the ten-piece preset's
+        let mut mask = solution.mask;
+        mask.set(0, 0, CellTag::Covered(9));
+        assert!(GameState::from_tagged_mask(Preset::Classic, &mask).err() == Some(ReconstructError::UnknownPiece(9)));
+        mask.set(0, 0, CellTag::Covered(0));
+        assert!(GameState::from_tagged_mask(Preset::TenPiece, &mask).err().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_shape() {
+        let target = OCT_17;
+        assert_eq!(serde_json::to_string(&target).unwrap(), r#"{"month":"October","day_of_week":"Saturday","day_of_month":17}"#);
+
+        let tags = [CellTag::Covered(3), CellTag::Winner, CellTag::Uncovered];
+        assert_eq!(serde_json::to_string(&tags).unwrap(), r#"[{"Covered":3},"Winner","Uncovered"]"#);
+
+        let mut game = GameState::new(Preset::Classic);
+        assert!(game.place_piece(1, Some(Placement{x: 0, y: 2, rotation: 3, flipped: true}), BoardMask::filled()));
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, r#"{"preset":"Classic","pieces":[null,{"x":0,"y":2,"rotation":3,"flipped":true},null,null,null,null,null,null,null]}"#);
+        assert!(serde_json::from_str::<GameState>(&json).unwrap() == game);
+
+        let overlapping = json.replace("null,null,null,null,null,null,null]", r#"{"x":0,"y":2,"rotation":3,"flipped":true},null,null,null,null,null,null]"#);
+        assert!(serde_json::from_str::<GameState>(&overlapping).is_err());
+        assert!(serde_json::from_str::<GameState>(r#"{"preset":"TenPiece","pieces":[]}"#).is_err());
+
+        let solve_date = |day_of_month| solve_first(TargetDate{day_of_month, ..OCT_17});
+        let solution = solve_date(17);
+        let mut value = serde_json::to_value(solution).unwrap();
+        assert!(serde_json::from_value::<Solution>(value.clone()).unwrap() == solution);
+        value["game"] = serde_json::to_value(solve_date(18).game).unwrap();
+        assert!(serde_json::from_value::<Solution>(value).is_err());
+    }
+
+    #[test]
+    fn test_parse_target_date() {
+        for input in ["2026-10-17", "Sat Oct 17", "October 17 Saturday", "17/10 sat", "saturday, OCTOBER 17th", "2026-10-17 Sat"] {
+            assert_eq!(input.parse::<TargetDate>(), Ok(OCT_17), "parsing {:?}", input);
+        }
+
+        assert_eq!("2024-02-29".parse::<TargetDate>().map(|t| t.day_of_week), Ok(Weekday::Thursday));
+        assert_eq!("1970-01-01".parse::<TargetDate>().map(|t| t.day_of_week), Ok(Weekday::Thursday));
+        assert_eq!("Tues Feb 29".parse::<TargetDate>().map(|t| t.day_of_month), Ok(29));
+
+        assert_eq!("Mon Feb 30".parse::<TargetDate>(), Err(ParseDateError::InvalidDay{month: Month::February, day: 30, year: None}));
+        assert_eq!("2025-02-29".parse::<TargetDate>(), Err(ParseDateError::InvalidDay{month: Month::February, day: 29, year: Some(2025)}));
+        assert_eq!("31/4 fri".parse::<TargetDate>(), Err(ParseDateError::InvalidDay{month: Month::April, day: 31, year: None}));
+        assert_eq!("17/13 fri".parse::<TargetDate>(), Err(ParseDateError::InvalidMonthNumber(13)));
+        assert_eq!("Oct 17".parse::<TargetDate>(), Err(ParseDateError::MissingWeekday));
+        assert_eq!("Oct 17 Mon Tue".parse::<TargetDate>(), Err(ParseDateError::DuplicateWeekday));
+        assert_eq!("Oct 17 Mo".parse::<TargetDate>(), Err(ParseDateError::UnrecognizedToken("Mo".to_string())));
+        assert_eq!("  ".parse::<TargetDate>(), Err(ParseDateError::Empty));
+        assert_eq!("2026-10-17 Sun".parse::<TargetDate>(), Err(ParseDateError::WeekdayMismatch{date: Weekday::Saturday, given: Weekday::Sunday}));
+        assert_eq!("Oct 17 2026".parse::<TargetDate>(), Err(ParseDateError::UnsupportedYear(2026)));
+    }
+
+    #[test]
+    fn test_calendar_date() {
+        let date = CalendarDate::from_ymd(2026, Month::October, 17).unwrap();
+        assert_eq!(date.weekday(), Weekday::Saturday);
+        assert_eq!(date.ymd(), (2026, Month::October, 17));
+        assert_eq!(date.to_string(), "2026-10-17");
+        assert_eq!(CalendarDate::from_days_since_epoch(date.days_since_epoch()), Some(date));
+        assert_eq!(CalendarDate::from_days_since_epoch(0), CalendarDate::from_ymd(1970, Month::January, 1));
+        assert_eq!(TargetDate::from_ymd(2000, Month::February, 29).map(|t| t.day_of_week), Some(Weekday::Tuesday));
+        assert_eq!(TargetDate::from_ymd(1900, Month::February, 29), None);
+        assert!(is_leap_year(2000) && is_leap_year(2024) && !is_leap_year(1900) && !is_leap_year(2026));
+
+        let new_years_eve = CalendarDate::from_ymd(2026, Month::December, 31).unwrap();
+        assert_eq!(new_years_eve.next(), CalendarDate::from_ymd(2027, Month::January, 1));
+        assert_eq!(new_years_eve.next().and_then(|date| date.prev()), Some(new_years_eve));
+
+        // the years end at i32::MAX and i32::MIN, instead of wrapping around
+        assert_eq!(CalendarDate::from_ymd(i32::MAX, Month::December, 31).unwrap().next(), None);
+        assert_eq!(CalendarDate::from_ymd(i32::MIN, Month::January, 1).unwrap().prev(), None);
+        assert_eq!(date.add_days(i64::MAX), None);
+
+        let week = date.iter_through(date.add_days(6).unwrap());
+        assert_eq!(week.len(), 7);
+        let weekdays = date.iter_through(date.add_days(6).unwrap()).rev().map(|d| d.weekday()).collect::<Vec<_>>();
+        assert_eq!(weekdays[0], Weekday::Friday);
+        assert_eq!(weekdays[6], Weekday::Saturday);
+
+        assert_eq!(CalendarDate::iter_year(2024).count(), 366);
+        assert_eq!(CalendarDate::iter_year(2025).rev().count(), 365);
+        let mut days = 0;
+        for (a, b) in CalendarDate::iter_year(1999).zip(CalendarDate::iter_year(1999).skip(1)) {
+            assert_eq!(a.next(), Some(b));
+            assert_eq!(b.days_since_epoch() - a.days_since_epoch(), 1);
+            days += 1;
+        }
+        assert_eq!(days, 364);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_chrono_conversions() {
+        let naive = chrono::NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let date = CalendarDate::from(naive);
+        assert_eq!(date, CalendarDate::from_ymd(2026, Month::October, 17).unwrap());
+        assert_eq!(chrono::NaiveDate::try_from(date), Ok(naive));
+        assert_eq!(TargetDate::from(naive).day_of_week, Weekday::Saturday);
+        for weekday in Weekday::ALL {
+            assert_eq!(Weekday::from(chrono::Weekday::from(weekday)), weekday);
+        }
+        for month in Month::ALL {
+            assert_eq!(Month::from(chrono::Month::from(month)), month);
+        }
+        let far_future = CalendarDate::from_ymd(i32::MAX, Month::January, 1).unwrap();
+        assert_eq!(chrono::NaiveDate::try_from(far_future), Err(DateOutOfRange(far_future)));
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_time_conversions() {
+        let date = time::Date::from_calendar_date(2026, time::Month::October, 17).unwrap();
+        let calendar_date = CalendarDate::from(date);
+        assert_eq!(calendar_date, CalendarDate::from_ymd(2026, Month::October, 17).unwrap());
+        assert_eq!(time::Date::try_from(calendar_date), Ok(date));
+        assert_eq!(TargetDate::from(date).day_of_week, Weekday::Saturday);
+        for weekday in Weekday::ALL {
+            assert_eq!(Weekday::from(time::Weekday::from(weekday)), weekday);
+        }
+        for month in Month::ALL {
+            assert_eq!(Month::from(time::Month::from(month)), month);
+        }
+    }
+
+    #[test]
+    fn test_locale_names() {
+        assert_eq!(Month::March.name(Locale::German), "März");
+        assert_eq!(Month::December.short_name(Locale::Spanish), "dic");
+        assert_eq!(Weekday::Thursday.name(Locale::French), "jeudi");
+        assert_eq!(Weekday::Sunday.short_name(Locale::Chinese), "周日");
+        assert_eq!(Month::October.to_string(), "October");
+
+        assert_eq!(BoardLabel::DayOfWeekLabel(Weekday::Tuesday).text(Locale::English), "TUE");
+        assert_eq!(BoardLabel::MonthLabel(Month::August).text(Locale::French), "AOÛT");
+        assert_eq!(BoardLabel::DayLabel(17).text(Locale::Chinese), "17");
+
+        let target = OCT_17;
+        assert_eq!(Localized::new(target, Locale::English).to_string(), "Saturday, October 17");
+        assert_eq!(Localized::new(target, Locale::German).to_string(), "Samstag, 17. Oktober");
+        assert_eq!(Localized::new(target, Locale::Chinese).to_string(), "10月17日 星期六");
+        assert_eq!(display_width("10月"), 4);
+
+        let mut mask = TaggedMask::default();
+        mask.set(0, 0, CellTag::Covered(4));
+        mask.set(1, 0, CellTag::Winner);
+        let first_row = mask.labeled(&BOARD_LABELS, Locale::English).to_string().lines().next().unwrap().to_string();
+        assert_eq!(first_row, " [4] *FEB* MAR  APR  MAY  JUN ");
+        let first_row = mask.labeled(&BOARD_LABELS, Locale::Chinese).to_string().lines().next().unwrap().to_string();
+        assert_eq!(first_row, " [4] *2月* 3月  4月  5月  6月 ");
+    }
+
+    #[test]
+    fn test_ansi_renderer() {
+        use crate::render::AnsiRenderer;
+
+        let mut mask = TaggedMask::default();
+        mask.set(0, 0, CellTag::Covered(4));
+        mask.set(1, 0, CellTag::Covered(4));
+        mask.set(2, 0, CellTag::Winner);
+
+        let rendered = AnsiRenderer::monochrome(Preset::Classic).render(&mask);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2 * PUZZLE_HEIGHT + 1);
+        assert_eq!(lines[0], "┌───────────┬─────┬─────┬─────┬─────┐");
+        assert_eq!(lines[1], "│  4     4  │*MAR*│ APR │ MAY │ JUN │");
+        assert_eq!(lines[2], "├─────┬─────┼─────┼─────┼─────┼─────┤");
+        assert!(!rendered.contains('\x1b'));
+
+        // Chinese characters take up two columns
+        let rendered = AnsiRenderer { locale: Locale::Chinese, ..AnsiRenderer::monochrome(Preset::Classic) }.render(&mask);
+        assert_eq!(rendered.lines().nth(1), Some("│  4     4  │*3月*│ 4月 │ 5月 │ 6月 │"));
+
+        let colored = AnsiRenderer::new(Preset::Classic).render(&mask);
+        assert!(colored.contains("\x1b[48;2;122;134;94m"), "piece 4 is not filled with its color");
+
+        // tags past the last piece color wrap around instead of panicking
+        mask.set(3, 0, CellTag::Covered(42));
+        assert!(AnsiRenderer::new(Preset::Classic).render(&mask).contains("\x1b[48;2;"));
+    }
+
+    #[test]
+    fn test_svg_renderer() {
+        use crate::render::{SvgRenderer, Theme};
+
+        let target = OCT_17;
+        let solution = solve_first(target);
+
+        let renderer = SvgRenderer { cell_size: 20, theme: Theme::PRINT, ..SvgRenderer::new(Preset::Classic) };
+        let svg = renderer.render_game(&solution.game, target).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(r#"width="140" height="200""#));
+        // the background and every cell
+        assert_eq!(svg.matches("<rect").count(), 1 + PUZZLE_WIDTH * PUZZLE_HEIGHT);
+        assert!(svg.contains(r##"fill="#bb6542""##), "piece 3 is not drawn in its color");
+        assert_eq!(svg.matches(r#"font-weight="bold""#).count(), 3);
+        assert!(svg.contains(">OCT</text>") && svg.contains(">17</text>") && svg.contains(">SAT</text>"));
+        assert!(renderer.render_game(&solution.game, TargetDate{day_of_month: 32, ..target}).is_none());
+
+        let mut mask = solution.mask;
+        mask.set(0, 0, CellTag::Covered(42));
+        assert!(renderer.render(&mask).contains(&format!(r#"fill="{}""#, Theme::PRINT.piece(2))));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png_renderer() {
+        use crate::render::{PngRenderer, PIECE_COLORS};
+
+        let mut mask = TaggedMask::default();
+        mask.set(0, 0, CellTag::Covered(4));
+        mask.set(1, 0, CellTag::Winner);
+
+        let renderer = PngRenderer { cell_size: 24, ..PngRenderer::new(Preset::Classic) };
+        let bytes = renderer.render(&mask);
+        let mut reader = png::Decoder::new(&bytes[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (7 * 24, 10 * 24));
+
+        // the middle of the first cell, which has no label drawn over it
+        let offset = ((12 + 12) * info.width as usize + (12 + 12)) * 3;
+        let crate::render::Rgb(r, g, b) = PIECE_COLORS[4];
+        assert_eq!(pixels[offset..offset + 3], [r, g, b]);
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_gif_renderer() {
+        use crate::render::GifRenderer;
+
+        let target = OCT_17;
+        let renderer = GifRenderer { cell_size: 8, max_frames: 50, ..GifRenderer::new(Preset::Classic) };
+        let bytes = renderer.render(solve(target)).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new().read_info(&bytes[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (7 * 8, 10 * 8));
+        let mut frames = 0;
+        let mut last_delay = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames += 1;
+            last_delay = frame.delay;
+        }
+
+        // the solver takes thousands of steps, so they're sampled down to fit, and the solution is shown the longest
+        assert!(frames > 25 && frames <= 50, "{} frames", frames);
+        assert_eq!(last_delay, renderer.final_delay);
+        assert!(renderer.render(std::iter::empty()).is_none());
+    }
+
+    // this solves every date of every preset, which takes several minutes even in release builds, so it only runs when
+    // asked for with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_presets_solve_every_date() {
+        for preset in Preset::ALL {
+            let unsolvable = preset.unsolvable_dates().collect::<Vec<_>>();
+            assert!(unsolvable.is_empty(), "{} cannot solve {:?}", preset, unsolvable);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use super::*;
+
+use alloc::string::{String, ToString};
+
+const CELL_WIDTH: usize = 5;
+
+/// Draws a [`TaggedMask`] for a terminal, with box-drawing lines around each piece and the board's labels in the cells
+/// which aren't covered.
+///
+/// With `color`, pieces are filled with the colors of the `theme` (using 24-bit ANSI escape codes) and the winning
+/// cells are highlighted. Without it, no escape codes are written: covered cells show the index of their piece, and the
+/// winning labels are marked with `*`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AnsiRenderer {
+    pub labels: &'static [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    pub locale: Locale,
+    pub color: bool,
+    pub theme: Theme,
+}
+
+impl AnsiRenderer {
+    pub fn new(preset: Preset) -> Self {
+        Self { labels: preset.labels(), locale: Locale::default(), color: true, theme: Theme::default() }
+    }
+
+    pub fn monochrome(preset: Preset) -> Self {
+        Self { color: false, ..Self::new(preset) }
+    }
+
+    pub fn render(&self, mask: &TaggedMask) -> String {
+        let mut out = String::new();
+        for y in 0..=PUZZLE_HEIGHT as isize {
+            self.write_boundary_row(&mut out, mask, y);
+            if y < PUZZLE_HEIGHT as isize {
+                out.push('\n');
+                self.write_cell_row(&mut out, mask, y);
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+
+    // the horizontal lines above row `y`
+    fn write_boundary_row(&self, out: &mut String, mask: &TaggedMask, y: isize) {
+        for x in 0..=PUZZLE_WIDTH as isize {
+            // the corner at the top left of the cell (x, y)
+            let up = has_boundary(mask, (x - 1, y - 1), (x, y - 1));
+            let down = has_boundary(mask, (x - 1, y), (x, y));
+            let left = has_boundary(mask, (x - 1, y - 1), (x - 1, y));
+            let right = has_boundary(mask, (x, y - 1), (x, y));
+            match box_char(up, down, left, right) {
+                ' ' => self.write_fill(out, piece_at(mask, x, y), 1),
+                c => out.push(c),
+            }
+
+            if x < PUZZLE_WIDTH as isize {
+                if right {
+                    out.push_str(&"─".repeat(CELL_WIDTH));
+                } else {
+                    self.write_fill(out, piece_at(mask, x, y), CELL_WIDTH);
+                }
+            }
+        }
+    }
+
+    fn write_cell_row(&self, out: &mut String, mask: &TaggedMask, y: isize) {
+        for x in 0..=PUZZLE_WIDTH as isize {
+            if has_boundary(mask, (x - 1, y), (x, y)) {
+                out.push('│');
+            } else {
+                self.write_fill(out, piece_at(mask, x, y), 1);
+            }
+
+            if x < PUZZLE_WIDTH as isize {
+                self.write_cell(out, mask.get(x as usize, y as usize), self.labels[y as usize][x as usize]);
+            }
+        }
+    }
+
+    fn write_cell(&self, out: &mut String, tag: CellTag, label: BoardLabel) {
+        let text = label.text(self.locale);
+        match (tag, self.color) {
+            (CellTag::Covered(piece_idx), true) => self.write_fill(out, Some(piece_idx), CELL_WIDTH),
+            (CellTag::Covered(piece_idx), false) => write_centered(out, &piece_idx.to_string()),
+            (CellTag::Winner, true) => {
+                write_style(out, Some(self.theme.label), self.theme.winner, true);
+                write_centered(out, &text);
+                out.push_str(RESET);
+            }
+            (CellTag::Winner, false) if display_width(&text) + 2 <= CELL_WIDTH => write_centered(out, &format!("*{}*", text)),
+            (CellTag::Winner, false) => write_centered(out, &text),
+            (CellTag::Uncovered, true) => {
+                write_style(out, Some(self.theme.label), self.theme.cell, false);
+                write_centered(out, &text);
+                out.push_str(RESET);
+            }
+            (CellTag::Uncovered, false) => write_centered(out, &text),
+        }
+    }
+
+    // spaces inside a piece, which take on its color
+    fn write_fill(&self, out: &mut String, piece: Option<u8>, width: usize) {
+        match piece.filter(|_| self.color) {
+            Some(piece_idx) => {
+                write_style(out, None, self.theme.piece(piece_idx), false);
+                out.push_str(&" ".repeat(width));
+                out.push_str(RESET);
+            }
+            None => out.push_str(&" ".repeat(width)),
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+fn write_style(out: &mut String, foreground: Option<Rgb>, background: Rgb, bold: bool) {
+    if bold {
+        out.push_str("\x1b[1m");
+    }
+    if let Some(Rgb(r, g, b)) = foreground {
+        out.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b));
+    }
+    let Rgb(r, g, b) = background;
+    out.push_str(&format!("\x1b[48;2;{};{};{}m", r, g, b));
+}
+
+fn write_centered(out: &mut String, text: &str) {
+    let padding = CELL_WIDTH.saturating_sub(display_width(text));
+    out.push_str(&" ".repeat(padding / 2));
+    out.push_str(text);
+    out.push_str(&" ".repeat(padding - padding / 2));
+}
+
+// the box-drawing character joining lines which leave a corner in the given directions
+fn box_char(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (true, true, false, false) => '│',
+        (false, false, true, true) => '─',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╷',
+        (false, false, true, false) => '╴',
+        (false, false, false, true) => '╶',
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+use alloc::vec::Vec;
+
+/// Animates the solver as a GIF image, from the messages of [`solve`] (or [`solve_preset`]) through to the solution.
+///
+/// Boards are drawn the same way as in PNG images.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GifRenderer {
+    pub labels: &'static [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    pub locale: Locale,
+    /// The width and height of each cell, in pixels.
+    pub cell_size: u32,
+    pub theme: Theme,
+    /// Only every `sample_every`th step of the solver becomes a frame.
+    pub sample_every: usize,
+    /// The most frames the animation has, including the last one.
+    ///
+    /// When the solver takes more steps than fit, the steps are sampled further apart, so the animation still covers
+    /// the whole solve.
+    pub max_frames: usize,
+    /// How long each frame is shown, in hundredths of a second.
+    pub frame_delay: u16,
+    /// How long the last frame is shown before the animation starts over, in hundredths of a second.
+    pub final_delay: u16,
+}
+
+impl GifRenderer {
+    pub fn new(preset: Preset) -> Self {
+        Self {
+            labels: preset.labels(),
+            locale: Locale::default(),
+            cell_size: 32,
+            theme: Theme::default(),
+            sample_every: 1,
+            max_frames: 200,
+            frame_delay: 5,
+            final_delay: 300,
+        }
+    }
+
+    /// The bytes of the GIF file. Returns `None` if there are no frames.
+    pub fn render(&self, messages: impl IntoIterator<Item=SolverMsg>) -> Option<Vec<u8>> {
+        let frames = self.sample(messages);
+        let (last, frames) = frames.split_last()?;
+
+        let palette = self.palette();
+        let flat_palette = palette.iter().flat_map(|Rgb(r, g, b)| [*r, *g, *b]).collect::<Vec<_>>();
+        let (width, height) = raster::board_size(self.cell_size as usize);
+        let mut encoder = ::gif::Encoder::new(Vec::new(), width as u16, height as u16, &flat_palette).ok()?;
+        encoder.set_repeat(::gif::Repeat::Infinite).ok()?;
+
+        let delays = frames.iter().map(|_| self.frame_delay).chain([self.final_delay]);
+        for (mask, delay) in frames.iter().chain([last]).zip(delays) {
+            let pixels = self.draw(mask, &palette);
+            let mut frame = ::gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+            frame.delay = delay;
+            encoder.write_frame(&frame).ok()?;
+        }
+
+        encoder.into_inner().ok()
+    }
+
+    // picks the frames to draw, always ending with the last one
+    fn sample(&self, messages: impl IntoIterator<Item=SolverMsg>) -> Vec<TaggedMask> {
+        let max_frames = self.max_frames.max(2);
+        let mut sample_every = self.sample_every.max(1);
+        let mut frames = Vec::with_capacity(max_frames);
+        let mut last = None;
+
+        for (step, message) in messages.into_iter().enumerate() {
+            let mask = match message {
+                SolverMsg::Unsolved(_, mask) => mask,
+                SolverMsg::Solved(solution) => solution.mask,
+                SolverMsg::Impossible => continue,
+            };
+
+            if step % sample_every == 0 {
+                // one slot is kept for the last frame
+                if frames.len() == max_frames - 1 {
+                    sample_every *= 2;
+                    let mut idx = 0;
+                    frames.retain(|_| {
+                        idx += 1;
+                        idx % 2 == 1
+                    });
+                }
+                if step % sample_every == 0 {
+                    frames.push(mask);
+                }
+            }
+            last = Some(mask);
+        }
+
+        // the last message may already have been sampled
+        if let Some(last) = last {
+            if frames.last() != Some(&last) {
+                frames.push(last);
+            }
+        }
+
+        frames
+    }
+
+    // the pixels of a frame, as indices into the palette
+    fn draw(&self, mask: &TaggedMask, palette: &[Rgb]) -> Vec<u8> {
+        let canvas = raster::draw_board(mask, self.labels, self.locale, self.cell_size as usize, &self.theme);
+        canvas.pixels.iter()
+            .map(|pixel| palette.iter().position(|color| color == pixel).unwrap_or(0) as u8)
+            .collect()
+    }
+
+    // every color a board is drawn with
+    fn palette(&self) -> Vec<Rgb> {
+        let theme = &self.theme;
+        [theme.background, theme.cell, theme.label, theme.winner, theme.outline].into_iter()
+            .chain(theme.pieces)
+            .collect()
+    }
+}
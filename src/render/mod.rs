@@ -0,0 +1,115 @@
+use crate::solver::*;
+
+use core::fmt;
+
+mod ansi;
+mod svg;
+#[cfg(any(feature = "png", feature = "gif"))]
+mod raster;
+#[cfg(feature = "png")]
+mod png;
+#[cfg(feature = "gif")]
+mod gif;
+
+pub use ansi::AnsiRenderer;
+pub use svg::SvgRenderer;
+#[cfg(feature = "png")]
+pub use self::png::PngRenderer;
+#[cfg(feature = "gif")]
+pub use self::gif::GifRenderer;
+
+/// A color, written as `#rrggbb`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The colors of the pieces, which match the `piece-N` classes of the web front-end.
+pub const PIECE_COLORS: [Rgb; MAX_PIECES] = [
+    Rgb(0x51, 0x74, 0x76),
+    Rgb(0x8e, 0x98, 0x80),
+    Rgb(0xc2, 0x4c, 0x3e),
+    Rgb(0xbb, 0x65, 0x42),
+    Rgb(0x7a, 0x86, 0x5e),
+    Rgb(0xa1, 0x58, 0x69),
+    Rgb(0x48, 0x53, 0x6f),
+    Rgb(0xbc, 0x8a, 0x8d),
+    Rgb(0xa9, 0xb5, 0x9f),
+    Rgb(0xd8, 0xb2, 0x6e),
+];
+
+/// The colors a board is drawn with.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Theme {
+    /// Behind the board.
+    pub background: Rgb,
+    /// The cells which aren't covered.
+    pub cell: Rgb,
+    pub label: Rgb,
+    /// The cells which show the date.
+    pub winner: Rgb,
+    /// The lines around pieces and cells.
+    pub outline: Rgb,
+    pub pieces: [Rgb; MAX_PIECES],
+}
+
+impl Theme {
+    /// The colors of the web front-end.
+    pub const WEB: Theme = Theme {
+        background: Rgb(0x38, 0x31, 0x27),
+        cell: Rgb(0xc1, 0x9a, 0x6b),
+        label: Rgb(0x3b, 0x27, 0x0c),
+        winner: Rgb(0xdc, 0x5b, 0x21),
+        outline: Rgb(0x3b, 0x27, 0x0c),
+        pieces: PIECE_COLORS,
+    };
+
+    /// A light theme for printing.
+    pub const PRINT: Theme = Theme {
+        background: Rgb(0xff, 0xff, 0xff),
+        cell: Rgb(0xf4, 0xee, 0xe2),
+        label: Rgb(0x22, 0x22, 0x22),
+        winner: Rgb(0xff, 0xd2, 0x7a),
+        outline: Rgb(0x22, 0x22, 0x22),
+        pieces: PIECE_COLORS,
+    };
+
+    /// The color of a piece. Indices past the last color, which no preset has but a [`TaggedMask`] can hold, wrap around.
+    pub fn piece(&self, piece_idx: u8) -> Rgb {
+        self.pieces[piece_idx as usize % MAX_PIECES]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::WEB
+    }
+}
+
+// the piece covering a cell, where the cell may be off the board
+fn piece_at(mask: &TaggedMask, x: isize, y: isize) -> Option<u8> {
+    if !is_on_board(x, y) {
+        return None;
+    }
+
+    match mask.get(x as usize, y as usize) {
+        CellTag::Covered(piece_idx) => Some(piece_idx),
+        _ => None,
+    }
+}
+
+fn is_on_board(x: isize, y: isize) -> bool {
+    (0..PUZZLE_WIDTH as isize).contains(&x) && (0..PUZZLE_HEIGHT as isize).contains(&y)
+}
+
+// whether a boundary is drawn between two neighbouring cells: every piece is outlined, and so is every cell which
+// isn't covered
+fn has_boundary(mask: &TaggedMask, a: (isize, isize), b: (isize, isize)) -> bool {
+    let a_piece = piece_at(mask, a.0, a.1);
+    let same_piece = a_piece.is_some() && a_piece == piece_at(mask, b.0, b.1);
+    !same_piece && (is_on_board(a.0, a.1) || is_on_board(b.0, b.1))
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+use alloc::vec::Vec;
+
+/// Draws a [`TaggedMask`] as a PNG image, with the same layout as the [`SvgRenderer`].
+///
+/// The labels are written in a small bitmap font, which has no glyphs for Chinese.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PngRenderer {
+    pub labels: &'static [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    pub locale: Locale,
+    /// The width and height of each cell, in pixels.
+    pub cell_size: u32,
+    pub theme: Theme,
+}
+
+impl PngRenderer {
+    pub fn new(preset: Preset) -> Self {
+        Self { labels: preset.labels(), locale: Locale::default(), cell_size: 48, theme: Theme::default() }
+    }
+
+    /// Draws the board of a game, with the cells showing `target` highlighted.
+    ///
+    /// The labels of the game's preset are used, whichever labels the renderer was created with. Returns `None` if the
+    /// preset can't show `target`.
+    pub fn render_game(&self, game: &GameState, target: TargetDate) -> Option<Vec<u8>> {
+        let winning_mask = game.preset().winning_mask(target)?;
+        let renderer = Self { labels: game.preset().labels(), ..*self };
+        Some(renderer.render(&game.tagged_mask(winning_mask)))
+    }
+
+    /// The bytes of the PNG file.
+    pub fn render(&self, mask: &TaggedMask) -> Vec<u8> {
+        let canvas = raster::draw_board(mask, self.labels, self.locale, self.cell_size as usize, &self.theme);
+
+        let mut out = Vec::new();
+        let mut encoder = ::png::Encoder::new(&mut out, canvas.width as u32, canvas.height as u32);
+        encoder.set_color(::png::ColorType::Rgb);
+        encoder.set_depth(::png::BitDepth::Eight);
+        // writing to a Vec can't fail, and the image data always matches the header
+        let mut writer = encoder.write_header().expect("failed to write the PNG header");
+        writer.write_image_data(&canvas.rgb_bytes()).expect("failed to write the PNG image data");
+        writer.finish().expect("failed to finish the PNG");
+
+        out
+    }
+}
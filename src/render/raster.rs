@@ -0,0 +1,166 @@
+use super::*;
+
+use alloc::vec::Vec;
+
+/// An image made of pixels, which the PNG and GIF renderers draw boards onto.
+pub(super) struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self { width, height, pixels: vec![background; width * height] }
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for row in y..(y + height).min(self.height) {
+            let start = row * self.width;
+            for pixel in &mut self.pixels[start + x.min(self.width)..start + (x + width).min(self.width)] {
+                *pixel = color;
+            }
+        }
+    }
+
+    // draws text in the bitmap font, centered on (center_x, center_y), with each dot of the font `scale` pixels wide
+    fn draw_text(&mut self, center_x: usize, center_y: usize, text: &str, scale: usize, color: Rgb, bold: bool) {
+        let glyphs = text.chars().filter_map(glyph).collect::<Vec<_>>();
+        let text_width = (glyphs.len() * (GLYPH_WIDTH + 1)).saturating_sub(1) * scale;
+        let left = center_x.saturating_sub(text_width / 2);
+        let top = center_y.saturating_sub(GLYPH_HEIGHT * scale / 2);
+        // bold text is drawn twice, slightly apart
+        let offsets: &[usize] = if bold { &[0, 1] } else { &[0] };
+
+        for (idx, rows) in glyphs.iter().enumerate() {
+            let glyph_left = left + idx * (GLYPH_WIDTH + 1) * scale;
+            for (row, bits) in rows.iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                        for offset in offsets {
+                            self.fill_rect(glyph_left + column * scale + offset, top + row * scale, scale, scale, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The pixels as bytes, 3 per pixel.
+    #[cfg(feature = "png")]
+    pub fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|Rgb(r, g, b)| [*r, *g, *b]).collect()
+    }
+}
+
+/// The width and height of a board drawn by [`draw_board`], in pixels.
+pub(super) fn board_size(cell_size: usize) -> (usize, usize) {
+    let margin = cell_size / 2;
+    (PUZZLE_WIDTH * cell_size + 2 * margin, PUZZLE_HEIGHT * cell_size + 2 * margin)
+}
+
+/// Draws a board the same way as the [`SvgRenderer`]: each piece filled in its color and outlined, and the labels
+/// of the cells which aren't covered written in a bitmap font.
+pub(super) fn draw_board(
+    mask: &TaggedMask,
+    labels: &[[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    locale: Locale,
+    cell_size: usize,
+    theme: &Theme,
+) -> Canvas {
+    let margin = cell_size / 2;
+    let (width, height) = board_size(cell_size);
+    let mut canvas = Canvas::new(width, height, theme.background);
+    let font_scale = (cell_size / 24).max(1);
+
+    for (x, y) in iter_coordinates() {
+        let (left, top) = (margin + x * cell_size, margin + y * cell_size);
+        let tag = mask.get(x, y);
+        let fill = match tag {
+            CellTag::Covered(piece_idx) => theme.piece(piece_idx),
+            CellTag::Winner => theme.winner,
+            CellTag::Uncovered => theme.cell,
+        };
+        canvas.fill_rect(left, top, cell_size, cell_size, fill);
+
+        if !matches!(tag, CellTag::Covered(_)) {
+            let text = labels[y][x].text(locale);
+            canvas.draw_text(left + cell_size / 2, top + cell_size / 2, &text, font_scale, theme.label, tag == CellTag::Winner);
+        }
+    }
+
+    let thickness = (cell_size / 16).max(1);
+    for y in 0..=PUZZLE_HEIGHT as isize {
+        for x in 0..=PUZZLE_WIDTH as isize {
+            // the lines are centered on the edges of the cells
+            let left = margin + x as usize * cell_size - thickness / 2;
+            let top = margin + y as usize * cell_size - thickness / 2;
+            if x < PUZZLE_WIDTH as isize && has_boundary(mask, (x, y - 1), (x, y)) {
+                canvas.fill_rect(left, top, cell_size + thickness, thickness, theme.outline);
+            }
+            if y < PUZZLE_HEIGHT as isize && has_boundary(mask, (x - 1, y), (x, y)) {
+                canvas.fill_rect(left, top, thickness, cell_size + thickness, theme.outline);
+            }
+        }
+    }
+
+    canvas
+}
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+// the rows of a 5x7 bitmap font, which covers the labels of the latin locales: accents are dropped, and characters
+// without a glyph are left out
+fn glyph(c: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    let c = match c.to_ascii_uppercase() {
+        'À' | 'Á' | 'Â' | 'Ä' | 'à' | 'á' | 'â' | 'ä' => 'A',
+        'È' | 'É' | 'Ê' | 'Ë' | 'è' | 'é' | 'ê' | 'ë' => 'E',
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'ì' | 'í' | 'î' | 'ï' => 'I',
+        'Ò' | 'Ó' | 'Ô' | 'Ö' | 'ò' | 'ó' | 'ô' | 'ö' => 'O',
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'ù' | 'ú' | 'û' | 'ü' => 'U',
+        'Ñ' | 'ñ' => 'N',
+        'Ç' | 'ç' => 'C',
+        c => c,
+    };
+
+    Some(match c {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        _ => return None,
+    })
+}
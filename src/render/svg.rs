@@ -0,0 +1,106 @@
+use super::*;
+
+use core::fmt::Write;
+use alloc::string::String;
+
+/// Draws a [`TaggedMask`] as an SVG document, with each piece filled in its color and outlined, and the board's labels
+/// in the cells which aren't covered.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SvgRenderer {
+    pub labels: &'static [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    pub locale: Locale,
+    /// The width and height of each cell, in pixels.
+    pub cell_size: u32,
+    pub theme: Theme,
+}
+
+impl SvgRenderer {
+    pub fn new(preset: Preset) -> Self {
+        Self { labels: preset.labels(), locale: Locale::default(), cell_size: 48, theme: Theme::default() }
+    }
+
+    /// Draws the board of a game, with the cells showing `target` highlighted.
+    ///
+    /// The labels of the game's preset are used, whichever labels the renderer was created with. Returns `None` if the
+    /// preset can't show `target`.
+    pub fn render_game(&self, game: &GameState, target: TargetDate) -> Option<String> {
+        let winning_mask = game.preset().winning_mask(target)?;
+        let renderer = Self { labels: game.preset().labels(), ..*self };
+        Some(renderer.render(&game.tagged_mask(winning_mask)))
+    }
+
+    pub fn render(&self, mask: &TaggedMask) -> String {
+        let cell_size = self.cell_size as usize;
+        let margin = cell_size / 2;
+        let width = PUZZLE_WIDTH * cell_size + 2 * margin;
+        let height = PUZZLE_HEIGHT * cell_size + 2 * margin;
+        let theme = &self.theme;
+
+        // writing to a String can't fail
+        let mut out = String::new();
+        let _ = writeln!(out, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#, w = width, h = height);
+        let _ = writeln!(out, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, theme.background);
+
+        let _ = writeln!(out, r#"<g shape-rendering="crispEdges">"#);
+        for (x, y) in iter_coordinates() {
+            let fill = match mask.get(x, y) {
+                CellTag::Covered(piece_idx) => theme.piece(piece_idx),
+                CellTag::Winner => theme.winner,
+                CellTag::Uncovered => theme.cell,
+            };
+            let _ = writeln!(out, r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                margin + x * cell_size, margin + y * cell_size, fill, s = cell_size);
+        }
+        let _ = writeln!(out, "</g>");
+
+        let _ = writeln!(out, r#"<g font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central" fill="{}">"#,
+            cell_size * 3 / 10, theme.label);
+        for (x, y) in iter_coordinates() {
+            let weight = match mask.get(x, y) {
+                CellTag::Covered(_) => continue,
+                CellTag::Winner => "bold",
+                CellTag::Uncovered => "normal",
+            };
+            let _ = writeln!(out, r#"<text x="{}" y="{}" font-weight="{}">{}</text>"#,
+                margin + x * cell_size + cell_size / 2,
+                margin + y * cell_size + cell_size / 2,
+                weight,
+                escape(&self.labels[y][x].text(self.locale)));
+        }
+        let _ = writeln!(out, "</g>");
+
+        // every boundary is a separate segment of one path
+        let mut path = String::new();
+        for y in 0..=PUZZLE_HEIGHT as isize {
+            for x in 0..=PUZZLE_WIDTH as isize {
+                let left = margin + x as usize * cell_size;
+                let top = margin + y as usize * cell_size;
+                if x < PUZZLE_WIDTH as isize && has_boundary(mask, (x, y - 1), (x, y)) {
+                    let _ = write!(path, "M{} {}h{}", left, top, cell_size);
+                }
+                if y < PUZZLE_HEIGHT as isize && has_boundary(mask, (x - 1, y), (x, y)) {
+                    let _ = write!(path, "M{} {}v{}", left, top, cell_size);
+                }
+            }
+        }
+        let _ = writeln!(out, r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="square"/>"#,
+            path, theme.outline, (cell_size / 16).max(1));
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
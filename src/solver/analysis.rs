@@ -0,0 +1,91 @@
+use super::prelude::*;
+
+/// How hard a single date is to solve.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DateAnalysis {
+    pub target: TargetDate,
+    /// The number of distinct solutions (see [`CanonicalSolution`]) for this date.
+    pub solutions: usize,
+    /// The number of steps the solver takes to reach its first solution, or `None` if there is no solution.
+    pub first_solution_steps: Option<usize>,
+}
+
+impl DateAnalysis {
+    pub fn is_solvable(&self) -> bool {
+        self.solutions > 0
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.solutions == 1
+    }
+}
+
+/// Enumerates every solution of `target`, which is much slower than finding the first one.
+pub fn analyze_date(preset: Preset, target: TargetDate) -> DateAnalysis {
+    let mut solutions = 0;
+    let mut first_solution_steps = None;
+    for solution in solve_all(preset, target) {
+        solutions += 1;
+        first_solution_steps.get_or_insert(solution.steps);
+    }
+
+    DateAnalysis { target, solutions, first_solution_steps }
+}
+
+/// Analyzes every one of the preset's [`Preset::valid_dates`].
+///
+/// Like [`Preset::unsolvable_dates`], this is meant to be run offline (it takes tens of minutes in release builds).
+pub fn analyze_calendar(preset: Preset) -> CalendarReport {
+    analyze_dates(preset, preset.valid_dates())
+}
+
+pub fn analyze_dates(preset: Preset, dates: impl IntoIterator<Item=TargetDate>) -> CalendarReport {
+    dates.into_iter().map(|target| analyze_date(preset, target)).collect()
+}
+
+/// The analysis of many dates, in the order they were analyzed.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CalendarReport {
+    pub dates: Vec<DateAnalysis>,
+}
+
+impl CalendarReport {
+    /// The `n` solvable dates which take the solver the most steps, hardest first.
+    pub fn hardest(&self, n: usize) -> Vec<DateAnalysis> {
+        let mut out = self.solvable().collect::<Vec<_>>();
+        out.sort_by_key(|analysis| core::cmp::Reverse(analysis.first_solution_steps));
+        out.truncate(n);
+        out
+    }
+
+    /// The `n` solvable dates which take the solver the fewest steps, easiest first.
+    pub fn easiest(&self, n: usize) -> Vec<DateAnalysis> {
+        let mut out = self.solvable().collect::<Vec<_>>();
+        out.sort_by_key(|analysis| analysis.first_solution_steps);
+        out.truncate(n);
+        out
+    }
+
+    /// The dates with exactly one solution.
+    pub fn unique(&self) -> impl Iterator<Item=DateAnalysis> + '_ {
+        self.dates.iter().copied().filter(DateAnalysis::is_unique)
+    }
+
+    pub fn unsolvable(&self) -> impl Iterator<Item=DateAnalysis> + '_ {
+        self.dates.iter().copied().filter(|analysis| !analysis.is_solvable())
+    }
+
+    pub fn solvable(&self) -> impl Iterator<Item=DateAnalysis> + '_ {
+        self.dates.iter().copied().filter(DateAnalysis::is_solvable)
+    }
+
+    pub fn get(&self, target: TargetDate) -> Option<&DateAnalysis> {
+        self.dates.iter().find(|analysis| analysis.target == target)
+    }
+}
+
+impl FromIterator<DateAnalysis> for CalendarReport {
+    fn from_iter<T: IntoIterator<Item=DateAnalysis>>(iter: T) -> Self {
+        Self { dates: iter.into_iter().collect() }
+    }
+}
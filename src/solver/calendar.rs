@@ -0,0 +1,200 @@
+use super::prelude::*;
+
+use core::fmt;
+
+/// A date in the (proleptic) Gregorian calendar.
+///
+/// Unlike [`TargetDate`], this knows its year, so the weekday is computed rather than stored, and it can be moved
+/// forwards and backwards across the ends of years. Dates are ordered chronologically.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct CalendarDate {
+    year: i32,
+    month: Month,
+    day_of_month: i8,
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+impl CalendarDate {
+    /// The date for the given year, month & day, if the month has that day in that year.
+    pub fn from_ymd(year: i32, month: Month, day_of_month: i8) -> Option<Self> {
+        if day_of_month < 1 || day_of_month > month.days_in_year(year) {
+            None
+        } else {
+            Some(Self { year, month, day_of_month })
+        }
+    }
+
+    /// The date `days` days after January 1st, 1970 (or before, when negative), if its year fits in an `i32`.
+    pub fn from_days_since_epoch(days: i64) -> Option<Self> {
+        // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days.checked_add(719_468)?;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day_of_month = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as i8;
+        let month_idx = if shifted_month < 10 { shifted_month + 2 } else { shifted_month - 10 };
+        let year = year_of_era + era * 400 + if month_idx < 2 { 1 } else { 0 };
+
+        Some(Self { year: i32::try_from(year).ok()?, month: Month::ALL[month_idx as usize], day_of_month })
+    }
+
+    /// The number of days since January 1st, 1970 (negative for dates before it).
+    pub fn days_since_epoch(&self) -> i64 {
+        // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month_idx = self.month as i64;
+        let year = self.year as i64 - if month_idx < 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = if month_idx >= 2 { month_idx - 2 } else { month_idx + 10 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day_of_month as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn day_of_month(&self) -> i8 {
+        self.day_of_month
+    }
+
+    pub fn ymd(&self) -> (i32, Month, i8) {
+        (self.year, self.month, self.day_of_month)
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // January 1st, 1970 was a Thursday
+        Weekday::ALL[(self.days_since_epoch() + 3).rem_euclid(7) as usize]
+    }
+
+    pub fn is_leap_year(&self) -> bool {
+        is_leap_year(self.year)
+    }
+
+    pub fn target(&self) -> TargetDate {
+        TargetDate { month: self.month, day_of_month: self.day_of_month, day_of_week: self.weekday() }
+    }
+
+    /// The date `days` days later (or earlier, when negative), unless that is past the range of years.
+    pub fn add_days(&self, days: i64) -> Option<Self> {
+        Self::from_days_since_epoch(self.days_since_epoch().checked_add(days)?)
+    }
+
+    /// The day after, unless this is the last day of year `i32::MAX`.
+    pub fn next(&self) -> Option<Self> {
+        self.add_days(1)
+    }
+
+    /// The day before, unless this is the first day of year `i32::MIN`.
+    pub fn prev(&self) -> Option<Self> {
+        self.add_days(-1)
+    }
+
+    /// Iterates every date from this one through `last` (inclusive), which may also be iterated in reverse.
+    pub fn iter_through(&self, last: CalendarDate) -> CalendarDateIter {
+        CalendarDateIter { front: *self, back: last, done: *self > last }
+    }
+
+    /// Iterates every date of `year`.
+    pub fn iter_year(year: i32) -> CalendarDateIter {
+        let first = Self { year, month: Month::January, day_of_month: 1 };
+        let last = Self { year, month: Month::December, day_of_month: 31 };
+        first.iter_through(last)
+    }
+}
+
+impl From<CalendarDate> for TargetDate {
+    fn from(value: CalendarDate) -> Self {
+        value.target()
+    }
+}
+
+impl TargetDate {
+    /// The target date for the given year, month & day, with the weekday filled in.
+    pub fn from_ymd(year: i32, month: Month, day_of_month: i8) -> Option<Self> {
+        CalendarDate::from_ymd(year, month, day_of_month).map(|date| date.target())
+    }
+
+    /// Finds this date in `year`, if it exists and falls on the same weekday.
+    pub fn in_year(&self, year: i32) -> Option<CalendarDate> {
+        CalendarDate::from_ymd(year, self.month, self.day_of_month)
+            .filter(|date| date.weekday() == self.day_of_week)
+    }
+}
+
+impl Month {
+    pub fn days_in_year(&self, year: i32) -> i8 {
+        self.number_days(is_leap_year(year))
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month as usize + 1, self.day_of_month)
+    }
+}
+
+pub struct CalendarDateIter {
+    front: CalendarDate,
+    back: CalendarDate,
+    done: bool,
+}
+
+impl Iterator for CalendarDateIter {
+    type Item = CalendarDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let current = self.front;
+        if current == self.back {
+            self.done = true;
+        } else {
+            self.front = current.next().expect("the back is after the front");
+        }
+
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if self.done {
+            0
+        } else {
+            (self.back.days_since_epoch() - self.front.days_since_epoch() + 1) as usize
+        };
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for CalendarDateIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let current = self.back;
+        if current == self.front {
+            self.done = true;
+        } else {
+            self.back = current.prev().expect("the front is before the back");
+        }
+
+        Some(current)
+    }
+}
+
+impl ExactSizeIterator for CalendarDateIter {}
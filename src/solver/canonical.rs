@@ -0,0 +1,48 @@
+use super::prelude::*;
+
+/// An arrangement of pieces, identified only by which cells each piece covers.
+///
+/// Different [`Placement`]s can cover exactly the same cells (for example, a piece which looks the same after being
+/// rotated), so two [`GameState`]s which look identical on the board may not compare equal. Their canonical forms do.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct CanonicalSolution {
+    preset: Preset,
+    cells: [BoardMask; MAX_PIECES],
+}
+
+impl CanonicalSolution {
+    pub fn preset(&self) -> Preset {
+        self.preset
+    }
+
+    /// The cells covered by the piece at `piece_idx`, which are empty if the piece isn't placed.
+    pub fn piece_cells(&self, piece_idx: usize) -> BoardMask {
+        self.cells.get(piece_idx).copied().unwrap_or_default()
+    }
+
+    pub fn mask(&self) -> BoardMask {
+        let mut out = BoardMask::default();
+        self.cells.iter().for_each(|cells| out.apply(*cells));
+        out
+    }
+}
+
+impl GameState {
+    pub fn canonical(&self) -> CanonicalSolution {
+        let preset = self.preset();
+        let mut cells = [BoardMask::default(); MAX_PIECES];
+        for (piece_idx, placement) in self.placements().iter().enumerate() {
+            if let Some(mask) = placement.and_then(|placement| preset.piece_mask(piece_idx, &placement)) {
+                cells[piece_idx] = mask;
+            }
+        }
+
+        CanonicalSolution { preset, cells }
+    }
+}
+
+impl From<GameState> for CanonicalSolution {
+    fn from(value: GameState) -> Self {
+        value.canonical()
+    }
+}
@@ -0,0 +1,86 @@
+use super::prelude::*;
+
+use core::fmt;
+
+/// A date outside of the range `chrono::NaiveDate` supports (about 262 thousand years either side of year 0).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DateOutOfRange(pub CalendarDate);
+
+impl fmt::Display for DateOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is outside of the dates chrono supports", self.0)
+    }
+}
+
+impl core::error::Error for DateOutOfRange {}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(value: chrono::Weekday) -> Self {
+        Weekday::ALL[value.num_days_from_monday() as usize]
+    }
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(value: Weekday) -> Self {
+        use chrono::Weekday as CW;
+        match value {
+            Weekday::Monday => CW::Mon,
+            Weekday::Tuesday => CW::Tue,
+            Weekday::Wednesday => CW::Wed,
+            Weekday::Thursday => CW::Thu,
+            Weekday::Friday => CW::Fri,
+            Weekday::Saturday => CW::Sat,
+            Weekday::Sunday => CW::Sun,
+        }
+    }
+}
+
+impl From<chrono::Month> for Month {
+    fn from(value: chrono::Month) -> Self {
+        Month::ALL[value.number_from_month() as usize - 1]
+    }
+}
+
+impl From<Month> for chrono::Month {
+    fn from(value: Month) -> Self {
+        use chrono::Month as CM;
+        match value {
+            Month::January => CM::January,
+            Month::February => CM::February,
+            Month::March => CM::March,
+            Month::April => CM::April,
+            Month::May => CM::May,
+            Month::June => CM::June,
+            Month::July => CM::July,
+            Month::August => CM::August,
+            Month::September => CM::September,
+            Month::October => CM::October,
+            Month::November => CM::November,
+            Month::December => CM::December,
+        }
+    }
+}
+
+impl From<chrono::NaiveDate> for CalendarDate {
+    fn from(value: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+        CalendarDate::from_ymd(value.year(), Month::ALL[value.month0() as usize], value.day() as i8)
+            .expect("chrono dates are valid")
+    }
+}
+
+impl From<chrono::NaiveDate> for TargetDate {
+    fn from(value: chrono::NaiveDate) -> Self {
+        CalendarDate::from(value).target()
+    }
+}
+
+/// Fails for years outside of the range chrono supports.
+impl TryFrom<CalendarDate> for chrono::NaiveDate {
+    type Error = DateOutOfRange;
+
+    fn try_from(value: CalendarDate) -> Result<Self, Self::Error> {
+        let (year, month, day) = value.ymd();
+        chrono::NaiveDate::from_ymd_opt(year, month as u32 + 1, day as u32).ok_or(DateOutOfRange(value))
+    }
+}
@@ -0,0 +1,194 @@
+use super::prelude::*;
+
+use core::fmt;
+
+/// The languages month and weekday names can be displayed in.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+    Chinese,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 5] = [Locale::English, Locale::German, Locale::French, Locale::Spanish, Locale::Chinese];
+
+    /// The name of the language, in that language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+            Locale::French => "Français",
+            Locale::Spanish => "Español",
+            Locale::Chinese => "中文",
+        }
+    }
+
+    fn month_names(&self) -> &'static [&'static str; 12] {
+        match self {
+            Locale::English => &["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"],
+            Locale::German => &["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"],
+            Locale::French => &["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"],
+            Locale::Spanish => &["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre"],
+            Locale::Chinese => &["一月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "十一月", "十二月"],
+        }
+    }
+
+    fn short_month_names(&self) -> &'static [&'static str; 12] {
+        match self {
+            Locale::English => &["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+            Locale::German => &["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+            Locale::French => &["janv", "févr", "mars", "avr", "mai", "juin", "juil", "août", "sept", "oct", "nov", "déc"],
+            Locale::Spanish => &["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"],
+            Locale::Chinese => &["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+        }
+    }
+
+    // these start on Monday, like Weekday::ALL
+    fn weekday_names(&self) -> &'static [&'static str; 7] {
+        match self {
+            Locale::English => &["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+            Locale::German => &["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+            Locale::French => &["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+            Locale::Spanish => &["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+            Locale::Chinese => &["星期一", "星期二", "星期三", "星期四", "星期五", "星期六", "星期日"],
+        }
+    }
+
+    fn short_weekday_names(&self) -> &'static [&'static str; 7] {
+        match self {
+            Locale::English => &["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            Locale::German => &["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+            Locale::French => &["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+            Locale::Spanish => &["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+            Locale::Chinese => &["周一", "周二", "周三", "周四", "周五", "周六", "周日"],
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Month {
+    pub fn name(&self, locale: Locale) -> &'static str {
+        locale.month_names()[*self as usize]
+    }
+
+    pub fn short_name(&self, locale: Locale) -> &'static str {
+        locale.short_month_names()[*self as usize]
+    }
+}
+
+impl Weekday {
+    pub fn name(&self, locale: Locale) -> &'static str {
+        locale.weekday_names()[*self as usize]
+    }
+
+    pub fn short_name(&self, locale: Locale) -> &'static str {
+        locale.short_weekday_names()[*self as usize]
+    }
+}
+
+impl BoardLabel {
+    /// The text printed on the board for this label: upper case short names, and plain day numbers.
+    pub fn text(&self, locale: Locale) -> String {
+        match self {
+            BoardLabel::MonthLabel(month) => month.short_name(locale).to_uppercase(),
+            BoardLabel::DayLabel(day) => day.to_string(),
+            BoardLabel::DayOfWeekLabel(weekday) => weekday.short_name(locale).to_uppercase(),
+            BoardLabel::Unlabeled => String::default(),
+        }
+    }
+}
+
+/// How many columns `text` takes up in a terminal, where Chinese characters (and other East Asian wide characters) are
+/// twice as wide as latin letters.
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if is_wide(c) { 2 } else { 1 }).sum()
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD)
+}
+
+/// A value which displays using its full name in a locale.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Localized<T> {
+    pub value: T,
+    pub locale: Locale,
+}
+
+impl<T> Localized<T> {
+    pub fn new(value: T, locale: Locale) -> Self {
+        Self { value, locale }
+    }
+}
+
+impl fmt::Display for Localized<Month> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.value.name(self.locale))
+    }
+}
+
+impl fmt::Display for Localized<Weekday> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.value.name(self.locale))
+    }
+}
+
+impl fmt::Display for Localized<TargetDate> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TargetDate { month, day_of_month, day_of_week } = self.value;
+        match self.locale {
+            Locale::English => write!(f, "{}, {} {}", day_of_week.name(self.locale), month.name(self.locale), day_of_month),
+            Locale::German => write!(f, "{}, {}. {}", day_of_week.name(self.locale), day_of_month, month.name(self.locale)),
+            Locale::French => write!(f, "{} {} {}", day_of_week.name(self.locale), day_of_month, month.name(self.locale)),
+            Locale::Spanish => write!(f, "{} {} de {}", day_of_week.name(self.locale), day_of_month, month.name(self.locale)),
+            Locale::Chinese => write!(f, "{}{}日 {}", month.short_name(self.locale), day_of_month, day_of_week.name(self.locale)),
+        }
+    }
+}
+
+/// Prints a tagged mask as a grid, with the board's labels shown in the cells which aren't covered.
+pub struct LabeledMask<'a> {
+    mask: &'a TaggedMask,
+    labels: &'a [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT],
+    locale: Locale,
+}
+
+impl TaggedMask {
+    pub fn labeled<'a>(&'a self, labels: &'a [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT], locale: Locale) -> LabeledMask<'a> {
+        LabeledMask { mask: self, labels, locale }
+    }
+}
+
+impl fmt::Display for LabeledMask<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CELL_WIDTH: usize = 5;
+        for (x, y) in iter_coordinates() {
+            let text = match self.mask.get(x, y) {
+                CellTag::Covered(piece_idx) => format!("[{}]", piece_idx),
+                CellTag::Winner => format!("*{}*", self.labels[y][x].text(self.locale)),
+                CellTag::Uncovered => self.labels[y][x].text(self.locale),
+            };
+
+            let padding = CELL_WIDTH.saturating_sub(display_width(&text));
+            write!(f, "{}{}{}", " ".repeat(padding / 2), text, " ".repeat(padding - padding / 2))?;
+
+            if x == PUZZLE_WIDTH - 1 && y != PUZZLE_HEIGHT - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
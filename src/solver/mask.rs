@@ -1,19 +1,23 @@
 use super::prelude::*;
 
-use std::fmt;
+use core::fmt;
 use fmt::Write;
 
-#[derive(Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoardMask(u64);
 
 impl BoardMask {
-    pub fn compute(positions: &[Option<Placement>; NUM_PIECES]) -> Self {
+    /// No cell is covered.
+    pub const EMPTY: BoardMask = BoardMask(0);
+
+    pub fn compute(preset: Preset, positions: &[Option<Placement>; MAX_PIECES]) -> Self {
         let mut out = Self::default();
         positions
             .iter()
             .enumerate()
             .filter_map(|(idx, placement)| placement.as_ref()
-                .and_then(|p| mask_for_piece(idx, p)))
+                .and_then(|p| preset.piece_mask(idx, p)))
             .for_each(|mask| out.apply(mask));
         out
     }
@@ -22,6 +26,20 @@ impl BoardMask {
         Self(u64::MAX >> (64 - (PUZZLE_WIDTH * PUZZLE_HEIGHT)))
     }
 
+    // every cell of the board which this doesn't cover
+    pub(super) fn inverted(&self) -> Self {
+        Self(!self.0 & Self::filled().0)
+    }
+
+    // the cells this covers which `other` doesn't
+    pub(super) fn without(&self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
     pub fn is_covered(&self, x: usize, y: usize) -> bool {
         self.0 & Self::mask(x, y) != 0
     }
@@ -72,7 +90,14 @@ impl fmt::Display for BoardMask {
     }
 }
 
+impl fmt::Debug for BoardMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellTag {
     Covered(u8),
     Winner,
@@ -80,6 +105,7 @@ pub enum CellTag {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaggedMask([[CellTag; PUZZLE_WIDTH]; PUZZLE_HEIGHT]);
 
 impl Default for TaggedMask {
@@ -135,4 +161,126 @@ impl fmt::Debug for TaggedMask {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self, f)
     }
-}
\ No newline at end of file
+}
+
+/// Why a [`BoardMask`] or [`TaggedMask`] could not be parsed. Rows and columns count from 0, skipping empty lines.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ParseMaskError {
+    WrongRowCount(usize),
+    WrongCellCount { row: usize, cells: usize },
+    InvalidCell { row: usize, column: usize, found: char },
+}
+
+impl fmt::Display for ParseMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMaskError::WrongRowCount(rows) => write!(f, "expected {} rows, found {}", PUZZLE_HEIGHT, rows),
+            ParseMaskError::WrongCellCount { row, cells } =>
+                write!(f, "expected {} cells in row {}, found {}", PUZZLE_WIDTH, row, cells),
+            ParseMaskError::InvalidCell { row, column, found } =>
+                write!(f, "unexpected {:?} in row {}, column {}", found, row, column),
+        }
+    }
+}
+
+impl core::error::Error for ParseMaskError {}
+
+/// Parses the output of `Display` (`[*]` for covered cells and `[ ]` for the others), or one character per cell with
+/// `*` or `#` for covered cells and `.` for the others. The rows may be indented, and may end in spaces.
+impl core::str::FromStr for BoardMask {
+    type Err = ParseMaskError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = parse_cells(s)?;
+        let mut out = Self::default();
+        for (x, y) in iter_coordinates() {
+            match cells[y][x] {
+                '*' | '#' => out.set_covered(x, y, true),
+                ' ' | '.' => {}
+                found => return Err(ParseMaskError::InvalidCell { row: y, column: x, found }),
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Parses the output of `Display` (`[3]` for a cell covered by piece 3, `[*]` for the winning cells and `[ ]` for the
+/// others), or one character per cell with `.` for the cells which aren't covered. The rows may be indented, and may
+/// end in spaces.
+impl core::str::FromStr for TaggedMask {
+    type Err = ParseMaskError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = parse_cells(s)?;
+        let mut out = Self::default();
+        for (x, y) in iter_coordinates() {
+            let tag = match cells[y][x] {
+                '*' => CellTag::Winner,
+                ' ' | '.' => CellTag::Uncovered,
+                c @ '0'..='9' if (c as usize - '0' as usize) < MAX_PIECES => CellTag::Covered(c as u8 - b'0'),
+                found => return Err(ParseMaskError::InvalidCell { row: y, column: x, found }),
+            };
+            out.set(x, y, tag);
+        }
+
+        Ok(out)
+    }
+}
+
+// splits a board into the character of each cell, where each row is either written as `[c]` per cell or as one
+// character per cell. The indentation shared by the rows is left out, as are spaces after the last cell.
+fn parse_cells(s: &str) -> Result<[[char; PUZZLE_WIDTH]; PUZZLE_HEIGHT], ParseMaskError> {
+    // a row of spaces is a row of cells which aren't covered, so only empty lines are skipped, and blank lines only
+    // when there are too many rows, like the indentation before the closing quote of a string
+    let mut rows = s.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+    while rows.len() > PUZZLE_HEIGHT && rows.last().is_some_and(|line| line.trim().is_empty()) {
+        rows.pop();
+    }
+    while rows.len() > PUZZLE_HEIGHT && rows.first().is_some_and(|line| line.trim().is_empty()) {
+        rows.remove(0);
+    }
+    if rows.len() != PUZZLE_HEIGHT {
+        return Err(ParseMaskError::WrongRowCount(rows.len()));
+    }
+
+    // spaces at the start of a compact row may be cells rather than indentation, so only as much indentation is left
+    // out as makes the longest row fit. When the last column is all spaces too, some of the indentation is taken for
+    // cells, since the text doesn't tell them apart
+    let is_bracketed = |line: &str| line.trim_start().starts_with('[');
+    let compact_rows = rows.iter().filter(|line| !is_bracketed(line));
+    let indent = compact_rows.clone().map(|line| line.chars().take_while(|c| c.is_whitespace()).count()).min().unwrap_or(0);
+    let longest = compact_rows.map(|line| line.trim_end().chars().count()).max().unwrap_or(0);
+    let indent = indent.min(longest.saturating_sub(PUZZLE_WIDTH));
+
+    let mut out = [[' '; PUZZLE_WIDTH]; PUZZLE_HEIGHT];
+    for (row, line) in rows.into_iter().enumerate() {
+        let cells = if is_bracketed(line) {
+            let chars = line.trim().chars().collect::<Vec<_>>();
+            let mut cells = Vec::with_capacity(PUZZLE_WIDTH);
+            for (column, chunk) in chars.chunks(3).enumerate() {
+                match chunk {
+                    ['[', c, ']'] => cells.push(*c),
+                    ['[', .., found] | [found, ..] =>
+                        return Err(ParseMaskError::InvalidCell { row, column, found: *found }),
+                    [] => unreachable!(),
+                }
+            }
+            cells
+        } else {
+            let mut chars = line.chars().skip(indent).collect::<Vec<_>>();
+            // spaces past the end of the row can't be cells, though spaces up to its end are
+            if chars.len() > PUZZLE_WIDTH && chars[PUZZLE_WIDTH..].iter().all(|c| c.is_whitespace()) {
+                chars.truncate(PUZZLE_WIDTH);
+            }
+            chars
+        };
+
+        if cells.len() != PUZZLE_WIDTH {
+            return Err(ParseMaskError::WrongCellCount { row, cells: cells.len() });
+        }
+        out[row].copy_from_slice(&cells);
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,118 @@
+use super::prelude::*;
+
+use core::hash::{Hash, Hasher};
+
+/// How a solver's memo of dead board states was used, see [`solve_memoized`].
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoStats {
+    /// How many boards the solver looked up before exploring them.
+    pub lookups: usize,
+    /// How many of those were known to be dead, and skipped.
+    pub hits: usize,
+    pub insertions: usize,
+    /// How many dead boards were forgotten to make room for another.
+    pub evictions: usize,
+}
+
+impl MemoStats {
+    /// The share of lookups which were hits, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.lookups as f64
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// the covered cells, and which pieces are placed
+type DeadState = (BoardMask, u16);
+
+/// Board states which the solver found can't be completed.
+///
+/// This is a hash table with room for one state in each of a fixed number of slots, so remembering a state evicts
+/// whichever state was in its slot.
+pub(super) struct DeadStates {
+    slots: Vec<Option<DeadState>>,
+    stats: MemoStats,
+}
+
+// a memo this large takes hundreds of megabytes, and rounding any larger capacity up to a power of two could overflow
+const MAX_SLOTS: usize = 1 << 24;
+
+impl DeadStates {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: vec![None; capacity.min(MAX_SLOTS).next_power_of_two()], stats: MemoStats::default() }
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn contains(&mut self, state: &GameState) -> bool {
+        let key = Self::key(state);
+        let hit = self.slots[self.slot(&key)] == Some(key);
+        self.stats.lookups += 1;
+        if hit {
+            self.stats.hits += 1;
+        }
+
+        hit
+    }
+
+    pub fn insert(&mut self, state: &GameState) {
+        let key = Self::key(state);
+        let slot = self.slot(&key);
+        if self.slots[slot].is_some_and(|evicted| evicted != key) {
+            self.stats.evictions += 1;
+        }
+        self.slots[slot] = Some(key);
+        self.stats.insertions += 1;
+    }
+
+    fn key(state: &GameState) -> DeadState {
+        let placed = state.all_placements().iter().enumerate()
+            .filter(|(_, placement)| placement.is_some())
+            .fold(0u16, |placed, (piece_idx, _)| placed | 1 << piece_idx);
+        (state.mask(), placed)
+    }
+
+    fn slot(&self, key: &DeadState) -> usize {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        // the slot count is a power of two, so the slot is the low bits of the hash. The hasher multiplies, and the low
+        // bits of a product only depend on the low bits of its factors, so the high half is folded into the low half
+        // first to let every bit of the key pick the slot
+        let hash = hasher.finish();
+        (hash ^ hash >> 32) as usize & (self.slots.len() - 1)
+    }
+}
+
+// the hash function of the Rust compiler, which is fast and good enough for keys which aren't picked by an attacker
+#[derive(Default)]
+struct FxHasher(u64);
+
+impl Hasher for FxHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_u64(*byte as u64);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.0 = (self.0.rotate_left(5) ^ value).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
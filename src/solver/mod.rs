@@ -3,10 +3,15 @@
 mod prelude {
     pub const PUZZLE_WIDTH: usize = 6;
     pub const PUZZLE_HEIGHT: usize = 9;
+    pub const MAX_PIECES: usize = 10;
+    /// The number of pieces of the classic preset.
+    #[deprecated(note = "presets have different numbers of pieces, use `Preset::num_pieces` or `MAX_PIECES` instead")]
     pub const NUM_PIECES: usize = 9;
 
-    use std::ops::Range;
-    pub use super::{state::*, solve::*, piece::*, mask::*, board::*, target::*};
+    use core::ops::Range;
+    // the solver only needs `alloc`, so these stand in for the parts of the std prelude it uses
+    pub use alloc::{boxed::Box, vec::Vec, string::{String, ToString}};
+    pub use super::{state::*, solve::*, piece::*, shape::*, mask::*, board::*, target::*, preset::*, canonical::*, analysis::*, table::*, share::*, parse::*, calendar::*, locale::*, memo::*, pattern::*, shown::*};
 
     pub fn iter_coordinates() -> impl Iterator<Item=(usize, usize)> {
         iter_coordinate_range(0..PUZZLE_WIDTH, 0..PUZZLE_HEIGHT)
@@ -20,12 +25,48 @@ mod prelude {
 mod state;
 mod solve;
 mod piece;
+mod shape;
 mod mask;
 mod target;
 mod board;
+mod preset;
+mod canonical;
+mod analysis;
+mod table;
+mod share;
+mod parse;
+mod calendar;
+mod locale;
+mod memo;
+mod pattern;
+mod shown;
+#[cfg(feature = "chrono")]
+mod chrono_compat;
+#[cfg(feature = "time")]
+mod time_compat;
 
 pub use board::{Weekday, Month, BOARD_LABELS, BoardLabel};
 pub use target::{TargetDate, TargetDateIter};
-pub use mask::{TaggedMask, CellTag};
-pub use solve::{Solution, SolverMsg, solve};
-pub use prelude::{PUZZLE_WIDTH, PUZZLE_HEIGHT, NUM_PIECES, iter_coordinates};
\ No newline at end of file
+pub use pattern::TargetPattern;
+pub use shown::ShownDateError;
+pub use parse::ParseDateError;
+pub use calendar::{CalendarDate, CalendarDateIter, is_leap_year};
+#[cfg(feature = "chrono")]
+pub use chrono_compat::DateOutOfRange;
+pub use locale::{Locale, Localized, LabeledMask, display_width};
+pub use mask::{TaggedMask, CellTag, BoardMask, ParseMaskError};
+pub use state::{GameState, Placement, ReconstructError};
+pub use piece::{Piece, Orientation, Rect};
+pub use shape::{Shape, ParseShapeError, ShapeError};
+pub use solve::{Solution, SolverMsg, solve, solve_for, solve_preset, solve_from, solve_memoized, solve_pattern, solve_pattern_pieces, solve_all, solve_pieces, PieceSolverMsg, PieceSolution};
+pub use memo::MemoStats;
+pub use canonical::CanonicalSolution;
+pub use share::{SharedSolution, ShareCodeError};
+pub use analysis::{DateAnalysis, CalendarReport, analyze_date, analyze_dates, analyze_calendar};
+pub use table::{TABLE_PRESET, generate_table};
+#[cfg(feature = "precomputed")]
+pub use table::lookup_solution;
+pub use preset::{Preset, DateSemantics, MIRRORED_BOARD_LABELS};
+pub use prelude::{PUZZLE_WIDTH, PUZZLE_HEIGHT, MAX_PIECES, iter_coordinates};
+#[allow(deprecated)]
+pub use prelude::NUM_PIECES;
\ No newline at end of file
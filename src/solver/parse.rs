@@ -0,0 +1,186 @@
+use super::prelude::*;
+
+use core::fmt;
+use core::str::FromStr;
+
+/// Why a [`TargetDate`], [`Month`] or [`Weekday`] could not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseDateError {
+    Empty,
+    UnrecognizedToken(String),
+    MissingMonth,
+    MissingDay,
+    MissingWeekday,
+    DuplicateMonth,
+    DuplicateDay,
+    DuplicateWeekday,
+    InvalidMonthNumber(i32),
+    /// A year was given outside of an ISO date, where it can't be told apart from a day.
+    UnsupportedYear(i32),
+    /// The weekday given next to an ISO date isn't the weekday of that date.
+    WeekdayMismatch { date: Weekday, given: Weekday },
+    /// The month (in the year, if one was given) doesn't have this day.
+    InvalidDay { month: Month, day: i32, year: Option<i32> },
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDateError::Empty => write!(f, "no date given"),
+            ParseDateError::UnrecognizedToken(token) => write!(f, "{:?} is not a month, weekday or day", token),
+            ParseDateError::MissingMonth => write!(f, "no month given"),
+            ParseDateError::MissingDay => write!(f, "no day of the month given"),
+            ParseDateError::MissingWeekday => write!(f, "no day of the week given"),
+            ParseDateError::DuplicateMonth => write!(f, "more than one month given"),
+            ParseDateError::DuplicateDay => write!(f, "more than one day of the month given"),
+            ParseDateError::DuplicateWeekday => write!(f, "more than one day of the week given"),
+            ParseDateError::InvalidMonthNumber(number) => write!(f, "there is no month #{}", number),
+            ParseDateError::UnsupportedYear(year) => write!(f, "the year {} can only be given in a date like {}-10-17", year, year),
+            ParseDateError::WeekdayMismatch { date, given } => write!(f, "the date is a {}, not a {}", date, given),
+            ParseDateError::InvalidDay { month, day, year: Some(year) } => write!(f, "{} {} has no day {}", month, year, day),
+            ParseDateError::InvalidDay { month, day, year: None } => write!(f, "{} has no day {}", month, day),
+        }
+    }
+}
+
+impl core::error::Error for ParseDateError {}
+
+/// Parses dates written like `2026-10-17`, `Sat Oct 17`, `October 17th, Saturday`, `17/10 sat` or `2026-10-17 Sat`.
+///
+/// Names are case-insensitive and may be abbreviated to any prefix of at least 3 letters. Numeric dates are read
+/// day first. Only ISO dates (`year-month-day`) may leave out the weekday, since it is computed from the year; a weekday
+/// given with one must match it.
+impl FromStr for TargetDate {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseDateError::Empty);
+        }
+
+        let mut month = None;
+        let mut day = None;
+        let mut weekday = None;
+        // the weekday of an ISO date, which is known from its year
+        let mut iso_weekday = None;
+
+        for token in s.split(|c: char| c.is_whitespace() || c == ',').filter(|token| !token.is_empty()) {
+            if let Some(date) = parse_iso(token) {
+                let date = date?;
+                set_once(&mut day, date.day_of_month as i32, ParseDateError::DuplicateDay)?;
+                set_once(&mut month, date.month, ParseDateError::DuplicateMonth)?;
+                iso_weekday = Some(date.day_of_week);
+            } else if token.len() >= 4 && token.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ParseDateError::UnsupportedYear(token.parse().unwrap_or(i32::MAX)));
+            } else if let Some((day_part, month_part)) = token.split_once('/') {
+                let (parsed_day, parsed_month) = parse_number(day_part).zip(parse_number(month_part))
+                    .ok_or_else(|| ParseDateError::UnrecognizedToken(token.to_string()))?;
+                set_once(&mut day, parsed_day, ParseDateError::DuplicateDay)?;
+                set_once(&mut month, month_from_number(parsed_month)?, ParseDateError::DuplicateMonth)?;
+            } else if let Some(parsed_day) = parse_number(token) {
+                set_once(&mut day, parsed_day, ParseDateError::DuplicateDay)?;
+            } else if let Some(parsed_month) = match_name(token, &Month::ALL) {
+                set_once(&mut month, parsed_month, ParseDateError::DuplicateMonth)?;
+            } else if let Some(parsed_weekday) = match_name(token, &Weekday::ALL) {
+                set_once(&mut weekday, parsed_weekday, ParseDateError::DuplicateWeekday)?;
+            } else {
+                return Err(ParseDateError::UnrecognizedToken(token.to_string()));
+            }
+        }
+
+        let month = month.ok_or(ParseDateError::MissingMonth)?;
+        let day = day.ok_or(ParseDateError::MissingDay)?;
+        let day_of_week = match (weekday, iso_weekday) {
+            (Some(given), Some(date)) if given != date => return Err(ParseDateError::WeekdayMismatch { date, given }),
+            (Some(weekday), _) | (None, Some(weekday)) => weekday,
+            (None, None) => return Err(ParseDateError::MissingWeekday),
+        };
+        let day_of_month = check_day(month, day, None)?;
+        Ok(TargetDate { month, day_of_month, day_of_week })
+    }
+}
+
+impl FromStr for Month {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(number) = parse_number(s) {
+            month_from_number(number)
+        } else {
+            match_name(s, &Month::ALL).ok_or_else(|| ParseDateError::UnrecognizedToken(s.to_string()))
+        }
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match_name(s, &Weekday::ALL).ok_or_else(|| ParseDateError::UnrecognizedToken(s.to_string()))
+    }
+}
+
+fn parse_iso(s: &str) -> Option<Result<TargetDate, ParseDateError>> {
+    let mut parts = s.splitn(3, '-');
+    let year = parts.next().filter(|year| year.len() == 4)?.parse::<i32>().ok()?;
+    let month = parts.next()?.parse::<i32>().ok()?;
+    let day = parts.next()?.parse::<i32>().ok()?;
+
+    Some(month_from_number(month).and_then(|month| {
+        let day_of_month = check_day(month, day, Some(year))?;
+        Ok(CalendarDate::from_ymd(year, month, day_of_month)
+            .expect("day was checked")
+            .target())
+    }))
+}
+
+// accepts plain numbers, and ordinals like 1st, 2nd, 3rd or 17th
+fn parse_number(token: &str) -> Option<i32> {
+    let lower = token.to_ascii_lowercase();
+    let digits = ["st", "nd", "rd", "th"].iter()
+        .find_map(|suffix| lower.strip_suffix(suffix))
+        .unwrap_or(&lower);
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+fn match_name<T: fmt::Debug + Copy>(token: &str, values: &[T]) -> Option<T> {
+    let token = token.trim_end_matches('.').to_lowercase();
+    if token.chars().count() < 3 {
+        return None;
+    }
+
+    values.iter().copied().find(|value| format!("{:?}", value).to_lowercase().starts_with(&token))
+}
+
+fn month_from_number(number: i32) -> Result<Month, ParseDateError> {
+    usize::try_from(number - 1).ok()
+        .and_then(|idx| Month::ALL.get(idx).copied())
+        .ok_or(ParseDateError::InvalidMonthNumber(number))
+}
+
+fn check_day(month: Month, day: i32, year: Option<i32>) -> Result<i8, ParseDateError> {
+    // without a year, February 29th is allowed
+    let leap_year = year.map(is_leap_year).unwrap_or(true);
+    if day < 1 || day > month.number_days(leap_year) as i32 {
+        Err(ParseDateError::InvalidDay { month, day, year })
+    } else {
+        Ok(day as i8)
+    }
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, err: ParseDateError) -> Result<(), ParseDateError> {
+    if slot.is_some() {
+        Err(err)
+    } else {
+        *slot = Some(value);
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use super::prelude::*;
+
+use alloc::collections::BTreeSet;
+
+/// A set of target dates, where each of the month, the day of the month and the day of the week is either any value or
+/// one of a set of values, along with the cells which may be left open besides the date. See [`solve_pattern`].
+///
+/// For example, `TargetPattern::from(target).with_any_weekday()` asks for the month and day of `target`, on whichever
+/// weekday the solver manages to leave open.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetPattern {
+    // one bit per month, per day of the month (bit 1 is the 1st) and per weekday
+    months: u16,
+    days: u32,
+    weekdays: u8,
+    // the cells which may stay open as well as the date's
+    #[cfg_attr(feature = "serde", serde(default))]
+    free_cells: BoardMask,
+}
+
+impl TargetPattern {
+    /// Matches every date.
+    pub const ANY: TargetPattern = TargetPattern {
+        months: (1 << 12) - 1,
+        days: u32::MAX << 1,
+        weekdays: (1 << 7) - 1,
+        free_cells: BoardMask::EMPTY,
+    };
+
+    pub fn with_months(self, months: impl IntoIterator<Item=Month>) -> Self {
+        Self { months: months.into_iter().fold(0, |bits, month| bits | 1 << month as u16), ..self }
+    }
+
+    /// Days outside of 1 to 31 are left out.
+    pub fn with_days(self, days: impl IntoIterator<Item=i8>) -> Self {
+        Self { days: days.into_iter().filter(|day| (1..=31).contains(day)).fold(0, |bits, day| bits | 1 << day), ..self }
+    }
+
+    pub fn with_weekdays(self, weekdays: impl IntoIterator<Item=Weekday>) -> Self {
+        Self { weekdays: weekdays.into_iter().fold(0, |bits, weekday| bits | 1 << weekday as u8), ..self }
+    }
+
+    pub fn with_any_month(self) -> Self {
+        Self { months: Self::ANY.months, ..self }
+    }
+
+    pub fn with_any_day(self) -> Self {
+        Self { days: Self::ANY.days, ..self }
+    }
+
+    pub fn with_any_weekday(self) -> Self {
+        Self { weekdays: Self::ANY.weekdays, ..self }
+    }
+
+    /// Lets the solver leave `free_cells` open as well as the cells showing the date. This only makes a difference for
+    /// pieces which leave more cells open than the date has, see [`solve_pattern_pieces`]. Free cells don't count
+    /// towards the date, even when they are labeled.
+    pub fn with_free_cells(self, free_cells: BoardMask) -> Self {
+        Self { free_cells, ..self }
+    }
+
+    pub fn free_cells(&self) -> BoardMask {
+        self.free_cells
+    }
+
+    pub fn matches(&self, target: TargetDate) -> bool {
+        (1..=31).contains(&target.day_of_month)
+            && self.months & 1 << target.month as u16 != 0
+            && self.days & 1 << target.day_of_month != 0
+            && self.weekdays & 1 << target.day_of_week as u8 != 0
+    }
+
+    /// The dates matching the pattern which `preset` can show, including February 29th. They are ordered by month, then
+    /// day, then weekday starting on Monday.
+    ///
+    /// Dates which leave the same cells open are only yielded once, so for presets which don't show the weekday, each
+    /// month & day is yielded with the first weekday in the pattern.
+    pub fn candidates(&self, preset: Preset) -> impl Iterator<Item=TargetDate> {
+        let pattern = *self;
+        let mut seen = BTreeSet::new();
+        Month::ALL.into_iter()
+            .flat_map(|month| (1..=month.number_days(true)).map(move |day_of_month| (month, day_of_month)))
+            .flat_map(|(month, day_of_month)| Weekday::ALL.map(|day_of_week| TargetDate { month, day_of_month, day_of_week }))
+            .filter(move |target| pattern.matches(*target))
+            .filter(move |target| preset.winning_mask(*target).is_some_and(|winning_mask| seen.insert(winning_mask)))
+    }
+
+    /// The date whose cells `solution` leaves open, if it matches the pattern.
+    pub fn solved_target(&self, solution: &Solution) -> Option<TargetDate> {
+        self.shown_target(solution.game.preset(), solution.game.mask())
+    }
+
+    /// The date shown by the cells which `mask` leaves uncovered besides the free cells, if it matches the pattern. On
+    /// presets which don't show the weekday, the date has the first weekday in the pattern, from Monday.
+    pub fn shown_target(&self, preset: Preset, mask: BoardMask) -> Option<TargetDate> {
+        let mut covered = mask;
+        covered.apply(self.free_cells);
+        let mut target = preset.shown_target(covered).ok()?;
+        if !preset.semantics().uses_weekday() {
+            target.day_of_week = Weekday::ALL.into_iter().find(|weekday| self.weekdays & 1 << *weekday as u8 != 0)?;
+        }
+
+        self.matches(target).then_some(target)
+    }
+
+    // the cells the solver may leave open: those of each month, day & weekday in the pattern which `preset` shows, and
+    // the free cells
+    pub(super) fn open_cells(&self, preset: Preset) -> BoardMask {
+        let mut out = self.free_cells;
+        for (x, y) in iter_coordinates() {
+            let allowed = match preset.labels()[y][x] {
+                BoardLabel::MonthLabel(month) => self.months & 1 << month as u16 != 0,
+                BoardLabel::DayLabel(day) => self.days & 1 << day != 0,
+                BoardLabel::DayOfWeekLabel(weekday) => preset.semantics().uses_weekday() && self.weekdays & 1 << weekday as u8 != 0,
+                BoardLabel::Unlabeled => false,
+            };
+            if allowed {
+                out.set_covered(x, y, true);
+            }
+        }
+
+        out
+    }
+
+    // how many of the `undecided` cells can still be left open along with the `open` ones: the free cells, and one for
+    // each part of the date which isn't open yet. None when a part of the date can't be left open anymore
+    pub(super) fn can_open(&self, preset: Preset, open: BoardMask, undecided: BoardMask) -> Option<usize> {
+        let labels = preset.labels();
+        let parts: [fn(BoardLabel) -> bool; 3] = [
+            |label| matches!(label, BoardLabel::MonthLabel(_)),
+            |label| matches!(label, BoardLabel::DayLabel(_)),
+            |label| matches!(label, BoardLabel::DayOfWeekLabel(_)),
+        ];
+        let num_parts = if preset.semantics().uses_weekday() { 3 } else { 2 };
+
+        let mut out = iter_coordinates().filter(|(x, y)| undecided.is_covered(*x, *y) && self.free_cells.is_covered(*x, *y)).count();
+        for part in &parts[..num_parts] {
+            let shows = |mask: BoardMask| iter_coordinates()
+                .any(|(x, y)| mask.is_covered(x, y) && !self.free_cells.is_covered(x, y) && part(labels[y][x]));
+            if !shows(open) {
+                if !shows(undecided) {
+                    return None;
+                }
+                out += 1;
+            }
+        }
+
+        Some(out)
+    }
+
+    // whether the cell at (x, y) can be left open along with the `open` cells, without showing two months, two days or
+    // two weekdays, or a day which the month doesn't have
+    pub(super) fn can_leave_open(&self, preset: Preset, open: BoardMask, x: usize, y: usize) -> bool {
+        if self.free_cells.is_covered(x, y) {
+            return true;
+        }
+
+        let labels = preset.labels();
+        let mut shown = iter_coordinates()
+            .filter(|(x, y)| open.is_covered(*x, *y) && !self.free_cells.is_covered(*x, *y))
+            .map(|(x, y)| labels[y][x]);
+        match labels[y][x] {
+            BoardLabel::MonthLabel(month) => shown.all(|label| match label {
+                BoardLabel::MonthLabel(_) => false,
+                BoardLabel::DayLabel(day) => day <= month.number_days(true),
+                _ => true,
+            }),
+            BoardLabel::DayLabel(day) => shown.all(|label| match label {
+                BoardLabel::DayLabel(_) => false,
+                BoardLabel::MonthLabel(month) => day <= month.number_days(true),
+                _ => true,
+            }),
+            BoardLabel::DayOfWeekLabel(_) => shown.all(|label| !matches!(label, BoardLabel::DayOfWeekLabel(_))),
+            BoardLabel::Unlabeled => false,
+        }
+    }
+}
+
+impl Default for TargetPattern {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+/// Matches exactly the date.
+impl From<TargetDate> for TargetPattern {
+    fn from(value: TargetDate) -> Self {
+        Self::ANY
+            .with_months([value.month])
+            .with_days([value.day_of_month])
+            .with_weekdays([value.day_of_week])
+    }
+}
@@ -50,78 +50,129 @@ lazy_static! {
     //                                  |
     // ---------------------------------+---------------------------------
     //
-    pub static ref PIECES: [Piece; NUM_PIECES] = [
-        Piece::with_mask([
+    pub static ref PIECES: [Piece; 9] = [
+        preset_piece([
             [true, true, false],
             [false, true, false],
             [true, true, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [true, false, false],
             [true, false, false],
             [true, true, true],
             [true, false, false]
         ]),
-        Piece::with_mask([
+        preset_piece([
             [true, true, true],
             [true, false, false],
             [true, false, false],
             [true, false, false],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [false, true],
             [true, true],
             [false, true],
             [false, true],
             [false, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [true, false],
             [true, true],
             [false, true],
             [false, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [false, true],
             [false, true],
             [false, true],
             [false, true],
             [true, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [false, true],
             [false, true],
             [false, true],
             [true, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [false, true],
             [true, true],
             [true, true],
         ]),
-        Piece::with_mask([
+        preset_piece([
             [true, true, false, false],
             [false, true, true, true],
             [false, true, false, false],
         ])
     ];
+
+    //
+    // the ten piece edition replaces "SHAPE 8" with the two pieces it can be cut into:
+    //
+    //    [][]                          |
+    //      []      "SHAPE 8a" (2x3)    |     [][]     "SHAPE 8b" (2x1)
+    //      []                          |
+    //
+    pub static ref TEN_PIECES: [Piece; 10] = [
+        PIECES[0], PIECES[1], PIECES[2], PIECES[3], PIECES[4], PIECES[5], PIECES[6], PIECES[7],
+        preset_piece([
+            [true, true],
+            [false, true],
+            [false, true],
+        ]),
+        preset_piece([
+            [true, true],
+        ]),
+    ];
+
+    //
+    // the month & day edition keeps the classic pieces, and adds a single square which covers the weekday
+    //
+    pub static ref MONTH_DAY_PIECES: [Piece; 10] = [
+        PIECES[0], PIECES[1], PIECES[2], PIECES[3], PIECES[4], PIECES[5], PIECES[6], PIECES[7], PIECES[8],
+        preset_piece([
+            [true],
+        ]),
+    ];
+}
+
+// the pieces of the presets are drawn by hand, and fit on the board
+fn preset_piece<const W: usize, const H: usize>(mask: [[bool; W]; H]) -> Piece {
+    Piece::with_mask(mask).expect("the preset pieces have cells, and fit on the board")
 }
 
 impl Piece {
-    pub fn with_mask<const W: usize, const H: usize>(mask: [[bool; W]; H]) -> Self {
+    /// Fails if the shape doesn't fit on the board as it's drawn, even if it would when turned.
+    pub fn new(shape: &Shape) -> Result<Self, ShapeError> {
+        if shape.width() > PUZZLE_WIDTH || shape.height() > PUZZLE_HEIGHT {
+            return Err(ShapeError::TooLarge { width: shape.width(), height: shape.height() });
+        }
+
         let mut masks = [None; Placement::NUM_PLACEMENTS];
-        for placement in Placement::iter_all() {
-            let code = placement.code().unwrap();
-            masks[code] = mask_for_placement(mask, &placement);
+        for orientation in Orientation::ALL {
+            let oriented = shape.oriented(orientation);
+            for (x, y) in iter_coordinates() {
+                let placement = orientation.at(x as u8, y as u8);
+                masks[placement.code().unwrap()] = oriented.mask_at(x, y);
+            }
         }
 
-        Self { masks, width: W, height: H }
+        Ok(Self { masks, width: shape.width(), height: shape.height() })
+    }
+
+    pub fn with_mask<const W: usize, const H: usize>(mask: [[bool; W]; H]) -> Result<Self, ShapeError> {
+        Self::new(&Shape::try_from(mask)?)
     }
 
     pub fn mask(&self, placement: &Placement) -> Option<BoardMask> {
         placement.code().and_then(|code| self.masks[code])
     }
 
+    pub fn area(&self) -> usize {
+        self.masks.iter().flatten().next().map(|mask| mask.count()).unwrap_or(0)
+    }
+
+    /// The width and height of the piece's bounding box, when rotated `rotation` quarter turns counter-clockwise.
     pub fn size(&self, rotation: u8) -> (usize, usize) {
         let is_odd_rotation = rotation % 2 == 1;
         if is_odd_rotation {
@@ -130,105 +181,96 @@ impl Piece {
             (self.width, self.height)
         }
     }
-}
-
-fn mask_for_placement<const W: usize, const H: usize>(mask: [[bool; W]; H], placement: &Placement) -> Option<BoardMask> {
-    let rotated_mask = transformed(mask, placement.rotation, placement.flipped);
-    let piece_width = rotated_mask.width();
-    let piece_height = rotated_mask.height();
-    let mut out = BoardMask::default();
-    for y_offset in 0..piece_height {
-        for x_offset in 0..piece_width {
-            let x = (placement.x as usize) + x_offset;
-            let y = (placement.y as usize) + y_offset;
-            if x >= PUZZLE_WIDTH || y >= PUZZLE_HEIGHT {
-                return None;
-            }
 
-            out.set_covered(x, y, rotated_mask.get(x_offset, y_offset));
-        }
+    /// The shape of the piece, in its default orientation.
+    pub fn shape(&self) -> Shape {
+        // pieces are made from shapes which fit on the board as they're drawn, so this has cells
+        Shape::from_cells(self.cells(Orientation::default()).collect())
     }
 
-    Some(out)
-}
-
-fn transformed<const W: usize, const H: usize>(mut mask: [[bool; W]; H], rotation: u8, flip: bool) -> RotatedMask<W, H> {
-    if flip {
-        mask = flipped(mask);
+    /// The cells covered by the piece in an orientation, as offsets from the top left of its bounding box, row by row.
+    ///
+    /// This yields nothing if the piece doesn't fit on the board in the orientation, with its top left at (0, 0), which
+    /// can only happen for turned pieces.
+    pub fn cells(&self, orientation: Orientation) -> impl Iterator<Item=(usize, usize)> {
+        let (width, height) = self.size(orientation.rotation);
+        let mask = self.mask(&orientation.at(0, 0)).unwrap_or_default();
+        iter_coordinate_range(0..width, 0..height).filter(move |(x, y)| mask.is_covered(*x, *y))
     }
 
-    use RotatedMask as RM;
-    let mut mask = RM::Horizontal(mask);
-    for _ in 0..(rotation % 4) {
-        mask = match mask {
-            RM::Horizontal(last_mask) => RM::Vertical(rotated(last_mask)),
-            RM::Vertical(last_mask) => RM::Horizontal(rotated(last_mask)),
-        };
+    /// The orientation in which the piece's cells, listed by [`Piece::cells`], come first in order. Pieces with the
+    /// same shape have the same cells in their normalized orientations, however they were drawn.
+    pub fn normalized(&self) -> Orientation {
+        self.shape().normalized()
     }
 
-    mask
-}
+    /// The cells of the piece in its [normalized](Piece::normalized) orientation.
+    pub fn normalized_cells(&self) -> Vec<(usize, usize)> {
+        self.shape().canonical().cells().to_vec()
+    }
 
-fn rotated<const W: usize, const H: usize, E: Default + Copy>(input: [[E; W]; H]) -> [[E; H]; W] {
-    let mut output = [[E::default(); H]; W];
-    for dst_x in (0..H).rev() {
-        for dst_y in 0..W {
-            output[dst_y][dst_x] = input[dst_x][W - dst_y - 1];
-        }
+    /// See [`Shape::symmetries`].
+    pub fn symmetries(&self) -> Vec<Orientation> {
+        self.shape().symmetries()
     }
-    output
-}
 
-fn flipped<const W: usize, const H: usize, E: Copy>(data: [[E; W]; H]) -> [[E; W]; H] {
-    let half_w = W / 2;
-    let mut out = data;
-    for x in 0..half_w {
-        for y in 0..H {
-            out[y][x] = data[y][W - 1 - x];
-            out[y][W - 1 - x] = data[y][x];
-        }
+    /// See [`Shape::orientations`].
+    pub fn orientations(&self) -> Vec<Orientation> {
+        self.shape().orientations()
     }
 
-    out
+    /// The cells of the board which enclose the piece when it's placed, or `None` if the placement is off the board.
+    pub fn bounding_box(&self, placement: &Placement) -> Option<Rect> {
+        self.mask(placement)?;
+        let (width, height) = self.size(placement.rotation);
+        Some(Rect { x: placement.x as usize, y: placement.y as usize, width, height })
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum RotatedMask<const W: usize, const H: usize> {
-    Horizontal([[bool; W]; H]),
-    Vertical([[bool; H]; W]),
+/// How a piece is turned over, then rotated counter-clockwise in quarter turns.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orientation {
+    pub rotation: u8,
+    pub flipped: bool,
 }
 
-impl<const W: usize, const H: usize> RotatedMask<W, H> {
-    fn width(&self) -> usize {
-        use RotatedMask::{Horizontal as Hz, Vertical as Vt};
-        match self {
-            Hz(_) => W,
-            Vt(_) => H,
-        }
-    }
+impl Orientation {
+    /// Every orientation, in the order [`Placement::iter_all`] uses at each cell.
+    pub const ALL: [Orientation; 8] = [
+        Orientation { rotation: 0, flipped: false },
+        Orientation { rotation: 0, flipped: true },
+        Orientation { rotation: 1, flipped: false },
+        Orientation { rotation: 1, flipped: true },
+        Orientation { rotation: 2, flipped: false },
+        Orientation { rotation: 2, flipped: true },
+        Orientation { rotation: 3, flipped: false },
+        Orientation { rotation: 3, flipped: true },
+    ];
 
-    fn height(&self) -> usize {
-        use RotatedMask::{Horizontal as Hz, Vertical as Vt};
-        match self {
-            Hz(_) => H,
-            Vt(_) => W,
-        }
+    /// Places a piece in this orientation with the top left of its bounding box at (x, y).
+    pub fn at(&self, x: u8, y: u8) -> Placement {
+        Placement { x, y, rotation: self.rotation, flipped: self.flipped }
     }
+}
 
-    fn get(&self, x: usize, y: usize) -> bool {
-        use RotatedMask::{Horizontal as Hz, Vertical as Vt};
-        match self {
-            Hz(data) => data[y][x],
-            Vt(data) => data[y][x]
-        }
+impl Placement {
+    pub fn orientation(&self) -> Orientation {
+        Orientation { rotation: self.rotation % 4, flipped: self.flipped }
     }
 }
 
-pub fn piece(piece_idx: usize) -> Option<&'static Piece> {
-    PIECES.get(piece_idx)
+/// A rectangle of cells on the board.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
 }
 
-pub fn mask_for_piece(piece_idx: usize, placement: &Placement) -> Option<BoardMask> {
-    piece(piece_idx).and_then(|piece| piece.mask(placement))
+impl Rect {
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
 }
-
@@ -0,0 +1,182 @@
+use super::prelude::*;
+use core::fmt;
+
+/// The editions of the calendar puzzle which the solver knows about.
+///
+/// Every preset shares the same board geometry, but may label the cells differently, use a different set of pieces,
+/// or give a different meaning to the cells which are left uncovered.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Preset {
+    /// The original board: 9 pieces, showing the month, the day, and the day of the week.
+    #[default]
+    Classic,
+    /// The original pieces on a board where the labels are mirrored left to right.
+    Mirrored,
+    /// The original board, with the largest piece cut in two, for 10 pieces in total.
+    TenPiece,
+    /// The original board with an extra single square piece, which covers the weekday so only the month and day show.
+    MonthDay,
+}
+
+/// What the cells left uncovered by a solution are expected to show.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DateSemantics {
+    MonthDayWeekday,
+    MonthDay,
+}
+
+impl DateSemantics {
+    pub fn uses_weekday(&self) -> bool {
+        matches!(self, DateSemantics::MonthDayWeekday)
+    }
+}
+
+pub const MIRRORED_BOARD_LABELS: [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT] = {
+    use {Month as M, Weekday as WD, BoardLabel::{MonthLabel as ML, DayLabel as DL, DayOfWeekLabel as WL, Unlabeled}};
+    [
+        [ML(M::June), ML(M::May), ML(M::April), ML(M::March), ML(M::February), ML(M::January)],
+        [ML(M::December), ML(M::November), ML(M::October), ML(M::September), ML(M::August), ML(M::July)],
+        [DL(6), DL(5), DL(4), DL(3), DL(2), DL(1)],
+        [DL(12), DL(11), DL(10), DL(9), DL(8), DL(7)],
+        [DL(18), DL(17), DL(16), DL(15), DL(14), DL(13)],
+        [DL(24), DL(23), DL(22), DL(21), DL(20), DL(19)],
+        [DL(30), DL(29), DL(28), DL(27), DL(26), DL(25)],
+        [WL(WD::Wednesday), WL(WD::Tuesday), WL(WD::Monday), Unlabeled, Unlabeled, DL(31)],
+        [WL(WD::Sunday), WL(WD::Saturday), WL(WD::Friday), WL(WD::Thursday), Unlabeled, Unlabeled]
+    ]
+};
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [Preset::Classic, Preset::Mirrored, Preset::TenPiece, Preset::MonthDay];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Classic => "Classic",
+            Preset::Mirrored => "Mirrored",
+            Preset::TenPiece => "Ten Piece",
+            Preset::MonthDay => "Month & Day",
+        }
+    }
+
+    pub fn labels(&self) -> &'static [[BoardLabel; PUZZLE_WIDTH]; PUZZLE_HEIGHT] {
+        match self {
+            Preset::Classic | Preset::TenPiece | Preset::MonthDay => &BOARD_LABELS,
+            Preset::Mirrored => &MIRRORED_BOARD_LABELS,
+        }
+    }
+
+    pub fn pieces(&self) -> &'static [Piece] {
+        match self {
+            Preset::Classic | Preset::Mirrored => &PIECES[..],
+            Preset::TenPiece => &TEN_PIECES[..],
+            Preset::MonthDay => &MONTH_DAY_PIECES[..],
+        }
+    }
+
+    pub fn semantics(&self) -> DateSemantics {
+        match self {
+            Preset::MonthDay => DateSemantics::MonthDay,
+            _ => DateSemantics::MonthDayWeekday,
+        }
+    }
+
+    pub fn num_pieces(&self) -> usize {
+        self.pieces().len()
+    }
+
+    pub fn piece(&self, piece_idx: usize) -> Option<&'static Piece> {
+        self.pieces().get(piece_idx)
+    }
+
+    pub fn piece_mask(&self, piece_idx: usize, placement: &Placement) -> Option<BoardMask> {
+        self.pieces().get(piece_idx).and_then(|piece| piece.mask(placement))
+    }
+
+    /// Computes the mask of cells which must be covered to show `target` on this preset's board.
+    ///
+    /// When the preset does not show the day of the week, the weekday of `target` is ignored.
+    pub fn winning_mask(&self, target: TargetDate) -> Option<BoardMask> {
+        if target.day_of_month > 31 || target.day_of_month < 1 {
+            return None;
+        }
+
+        let uses_weekday = self.semantics().uses_weekday();
+        let mut out = BoardMask::filled();
+
+        let mut has_month = false;
+        let mut has_day_of_week = !uses_weekday;
+        let mut has_day = false;
+
+        for (x, y) in iter_coordinates() {
+            match self.labels()[y][x] {
+                BoardLabel::MonthLabel(month) if month == target.month => {
+                    if has_month {
+                        panic!("duplicate month label for {:?}", month);
+                    }
+                    out.set_covered(x, y, false);
+                    has_month = true;
+                }
+                BoardLabel::DayOfWeekLabel(weekday) if uses_weekday && weekday == target.day_of_week => {
+                    if has_day_of_week {
+                        panic!("duplicate weekday label for {:?}", weekday);
+                    }
+                    out.set_covered(x, y, false);
+                    has_day_of_week = true;
+                }
+                BoardLabel::DayLabel(day) if day == target.day_of_month => {
+                    if has_day {
+                        panic!("duplicate day label for {}", day);
+                    }
+                    out.set_covered(x, y, false);
+                    has_day = true;
+                }
+                _ => {}
+            }
+        }
+
+        if has_month && has_day_of_week && has_day {
+            Some(out)
+        } else {
+            None
+        }
+    }
+
+    /// Every date this preset is expected to be able to show, including February 29th.
+    ///
+    /// Presets which do not show the day of the week only yield each month & day once (with a weekday of Monday).
+    pub fn valid_dates(&self) -> impl Iterator<Item=TargetDate> {
+        let weekdays: &'static [Weekday] = if self.semantics().uses_weekday() {
+            &Weekday::ALL
+        } else {
+            &Weekday::ALL[..1]
+        };
+
+        weekdays.iter().flat_map(|day_of_week| TargetDateIter::create(
+            TargetDate { month: Month::January, day_of_month: 1, day_of_week: *day_of_week },
+            true))
+    }
+
+    /// Solves every one of [`Preset::valid_dates`], yielding the dates which have no solution.
+    ///
+    /// This takes a while (minutes, in release builds), so it is meant for verifying new presets, not for use at runtime.
+    pub fn unsolvable_dates(&self) -> impl Iterator<Item=TargetDate> {
+        let preset = *self;
+        self.valid_dates()
+            .filter(move |target| !matches!(solve_preset(preset, *target).last(), Some(SolverMsg::Solved(_))))
+    }
+
+    /// Whether the pieces of this preset have exactly the area of the cells which must be covered for any date.
+    pub fn is_area_consistent(&self) -> bool {
+        let piece_area: usize = self.pieces().iter().map(|piece| piece.area()).sum();
+        self.valid_dates()
+            .filter_map(|target| self.winning_mask(target))
+            .all(|winning_mask| winning_mask.count() == piece_area)
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
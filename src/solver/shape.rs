@@ -0,0 +1,241 @@
+use super::prelude::*;
+
+use core::fmt;
+use core::str::FromStr;
+
+/// A polyomino: a set of cells, moved so that its bounding box starts at (0, 0).
+///
+/// The cells are kept in order, row by row, so two shapes are equal when they cover the same cells. Use
+/// [`Shape::is_congruent`] to also count rotated and turned over shapes as equal.
+///
+/// Shapes are written as one line per row, with `#` for each cell of the shape and `.` for the gaps:
+///
+/// ```text
+/// ##.
+/// .#.
+/// ###
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape {
+    cells: Vec<(usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ParseShapeError {
+    Empty,
+    InvalidCharacter(char),
+}
+
+/// Why a [`Shape`], or a [`Piece`] of a shape, could not be made.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShapeError {
+    /// There are no cells.
+    Empty,
+    /// The shape is wider or taller than the board, as it's drawn.
+    TooLarge { width: usize, height: usize },
+}
+
+impl Shape {
+    /// Creates a shape from any set of cells, moving them to the top left and dropping duplicates.
+    pub fn new(cells: impl IntoIterator<Item=(usize, usize)>) -> Result<Self, ShapeError> {
+        let cells = cells.into_iter().collect::<Vec<_>>();
+        if cells.is_empty() {
+            return Err(ShapeError::Empty);
+        }
+
+        Ok(Self::from_cells(cells))
+    }
+
+    // the cells must not be empty
+    pub(super) fn from_cells(mut cells: Vec<(usize, usize)>) -> Self {
+        let min_x = cells.iter().map(|(x, _)| *x).min().unwrap_or(0);
+        let min_y = cells.iter().map(|(_, y)| *y).min().unwrap_or(0);
+        for (x, y) in &mut cells {
+            *x -= min_x;
+            *y -= min_y;
+        }
+        cells.sort_by_key(|(x, y)| (*y, *x));
+        cells.dedup();
+
+        let width = cells.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+        Self { cells, width, height }
+    }
+
+    /// The cells of the shape, as offsets from the top left of its bounding box, row by row.
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.cells.binary_search_by_key(&(y, x), |(x, y)| (*y, *x)).is_ok()
+    }
+
+    /// The shape turned a quarter turn counter-clockwise.
+    pub fn rotated(&self) -> Self {
+        Self::from_cells(self.cells.iter().map(|(x, y)| (*y, self.width - 1 - x)).collect())
+    }
+
+    /// The shape turned over, mirroring it from left to right.
+    pub fn reflected(&self) -> Self {
+        Self::from_cells(self.cells.iter().map(|(x, y)| (self.width - 1 - x, *y)).collect())
+    }
+
+    /// The shape turned over if the orientation is flipped, then rotated.
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        let mut out = if orientation.flipped { self.reflected() } else { self.clone() };
+        for _ in 0..(orientation.rotation % 4) {
+            out = out.rotated();
+        }
+
+        out
+    }
+
+    /// The cells of the shape moved so the top left of its bounding box is at (x, y).
+    pub fn translated(&self, x: usize, y: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.cells.iter().map(move |(dx, dy)| (x + dx, y + dy))
+    }
+
+    /// The cells of the board covered by the shape with the top left of its bounding box at (x, y), or `None` if any of
+    /// them are off the board.
+    pub fn mask_at(&self, x: usize, y: usize) -> Option<BoardMask> {
+        let mut out = BoardMask::default();
+        for (x, y) in self.translated(x, y) {
+            if x >= PUZZLE_WIDTH || y >= PUZZLE_HEIGHT {
+                return None;
+            }
+            out.set_covered(x, y, true);
+        }
+
+        Some(out)
+    }
+
+    /// The orientation in which the shape's cells come first in order.
+    pub fn normalized(&self) -> Orientation {
+        Orientation::ALL.into_iter()
+            .min_by_key(|orientation| self.oriented(*orientation).cells.iter().map(|(x, y)| (*y, *x)).collect::<Vec<_>>())
+            .unwrap_or_default()
+    }
+
+    /// The shape in its [normalized](Shape::normalized) orientation, which is the same for all congruent shapes.
+    pub fn canonical(&self) -> Self {
+        self.oriented(self.normalized())
+    }
+
+    /// Whether the shapes are the same after rotating or turning over one of them.
+    pub fn is_congruent(&self, other: &Shape) -> bool {
+        self.area() == other.area() && self.canonical() == other.canonical()
+    }
+
+    /// The orientations which leave the shape looking the same: its symmetry group, which always contains the default
+    /// orientation.
+    pub fn symmetries(&self) -> Vec<Orientation> {
+        Orientation::ALL.into_iter().filter(|orientation| self.oriented(*orientation) == *self).collect()
+    }
+
+    /// The orientations which each look different, so any other orientation looks the same as one of these.
+    pub fn orientations(&self) -> Vec<Orientation> {
+        let mut seen: Vec<Shape> = Vec::new();
+        let mut out = Vec::new();
+        for orientation in Orientation::ALL {
+            let oriented = self.oriented(orientation);
+            if !seen.contains(&oriented) {
+                seen.push(oriented);
+                out.push(orientation);
+            }
+        }
+
+        out
+    }
+}
+
+impl<const W: usize, const H: usize> TryFrom<[[bool; W]; H]> for Shape {
+    type Error = ShapeError;
+
+    fn try_from(value: [[bool; W]; H]) -> Result<Self, Self::Error> {
+        Self::new(iter_coordinate_range(0..W, 0..H).filter(|(x, y)| value[*y][*x]))
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                f.write_str(if self.contains(x, y) { "#" } else { "." })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+impl fmt::Display for ParseShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseShapeError::Empty => write!(f, "the shape has no cells"),
+            ParseShapeError::InvalidCharacter(c) => write!(f, "unexpected {:?}, cells are written as '#' and gaps as '.'", c),
+        }
+    }
+}
+
+impl core::error::Error for ParseShapeError {}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::Empty => write!(f, "the shape has no cells"),
+            ShapeError::TooLarge { width, height } =>
+                write!(f, "the shape is {}x{}, which doesn't fit on the {}x{} board", width, height, PUZZLE_WIDTH, PUZZLE_HEIGHT),
+        }
+    }
+}
+
+impl core::error::Error for ShapeError {}
+
+/// Parses a shape written as one line per row, with `#` (or `*`) for each cell and `.` (or a space) for the gaps.
+/// Blank lines and the indentation shared by every row are ignored.
+impl FromStr for Shape {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' | '*' => cells.push((x, y)),
+                    '.' | ' ' | '\t' => {}
+                    c => return Err(ParseShapeError::InvalidCharacter(c)),
+                }
+            }
+        }
+
+        if cells.is_empty() {
+            return Err(ParseShapeError::Empty);
+        }
+
+        Ok(Self::from_cells(cells))
+    }
+}
@@ -0,0 +1,230 @@
+use super::prelude::*;
+
+use core::fmt;
+use core::str::FromStr;
+
+//
+// A share code packs the preset, the target date and every piece's placement code into a bit string, which is then
+// written out 6 bits at a time using the URL-safe base64 alphabet (without padding):
+//
+//   [preset: 3][month: 4][day of month: 5][weekday: 3] then for each piece of the preset: [placement code: 9]
+//
+// a placement code of all ones (511) means the piece isn't placed. For the classic preset this is 16 characters.
+//
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const PRESET_BITS: u32 = 3;
+const MONTH_BITS: u32 = 4;
+const DAY_BITS: u32 = 5;
+const WEEKDAY_BITS: u32 = 3;
+const PLACEMENT_BITS: u32 = 9;
+const UNPLACED: u32 = (1 << PLACEMENT_BITS) - 1;
+
+/// An arrangement of pieces on the board for a date, which can be shared as a short code.
+///
+/// The code is produced by `Display`, and read back with `FromStr`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SharedSolution {
+    pub target: TargetDate,
+    pub game: GameState,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShareCodeError {
+    InvalidCharacter(char),
+    TooShort,
+    TooLong,
+    /// The bits after the last placement aren't all zero, so the code wasn't written by [`SharedSolution`].
+    InvalidPadding,
+    UnknownPreset(u8),
+    InvalidMonth(u8),
+    InvalidWeekday(u8),
+    /// The date can't be shown on the board of the preset.
+    InvalidDate(TargetDate),
+    /// The placement of the piece is not on the board.
+    InvalidPlacement(usize),
+    /// The piece overlaps another piece, or covers one of the cells which show the date.
+    Conflict(usize),
+}
+
+impl SharedSolution {
+    pub fn new(target: TargetDate, game: GameState) -> Self {
+        Self { target, game }
+    }
+
+    /// Whether every cell except for those showing the date is covered.
+    pub fn is_solved(&self) -> bool {
+        self.game.preset().winning_mask(self.target) == Some(self.game.mask())
+    }
+
+    pub fn tagged_mask(&self) -> TaggedMask {
+        self.game.tagged_mask(self.game.preset().winning_mask(self.target).unwrap_or_else(BoardMask::filled))
+    }
+}
+
+impl Solution {
+    pub fn shared(&self, target: TargetDate) -> SharedSolution {
+        SharedSolution::new(target, self.game)
+    }
+}
+
+impl fmt::Display for SharedSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let preset = self.game.preset();
+        let mut bits = BitWriter::default();
+        bits.write(preset_idx(preset) as u32, PRESET_BITS);
+        bits.write(self.target.month as u32, MONTH_BITS);
+        bits.write(self.target.day_of_month as u32, DAY_BITS);
+        bits.write(self.target.day_of_week as u32, WEEKDAY_BITS);
+        for placement in self.game.placements() {
+            let code = placement.and_then(|placement| placement.code()).map(|code| code as u32).unwrap_or(UNPLACED);
+            bits.write(code, PLACEMENT_BITS);
+        }
+
+        f.write_str(&bits.finish())
+    }
+}
+
+impl fmt::Debug for SharedSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl FromStr for SharedSolution {
+    type Err = ShareCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bits = BitReader::create(s.trim())?;
+
+        let preset_idx = bits.read(PRESET_BITS)? as u8;
+        let preset = *Preset::ALL.get(preset_idx as usize).ok_or(ShareCodeError::UnknownPreset(preset_idx))?;
+
+        let month_idx = bits.read(MONTH_BITS)? as u8;
+        let month = *Month::ALL.get(month_idx as usize).ok_or(ShareCodeError::InvalidMonth(month_idx))?;
+        let day_of_month = bits.read(DAY_BITS)? as i8;
+        let weekday_idx = bits.read(WEEKDAY_BITS)? as u8;
+        let day_of_week = *Weekday::ALL.get(weekday_idx as usize).ok_or(ShareCodeError::InvalidWeekday(weekday_idx))?;
+
+        let target = TargetDate { month, day_of_month, day_of_week };
+        let winning_mask = preset.winning_mask(target).ok_or(ShareCodeError::InvalidDate(target))?;
+
+        let mut game = GameState::new(preset);
+        for piece_idx in 0..preset.num_pieces() {
+            let code = bits.read(PLACEMENT_BITS)?;
+            if code == UNPLACED {
+                continue;
+            }
+
+            let placement = Placement::from_code(code as usize)
+                .filter(|placement| preset.piece_mask(piece_idx, placement).is_some())
+                .ok_or(ShareCodeError::InvalidPlacement(piece_idx))?;
+            if !game.place_piece(piece_idx, Some(placement), winning_mask) {
+                return Err(ShareCodeError::Conflict(piece_idx));
+            }
+        }
+
+        bits.finish()?;
+        Ok(Self { target, game })
+    }
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareCodeError::InvalidCharacter(c) => write!(f, "invalid character {:?} in share code", c),
+            ShareCodeError::TooShort => write!(f, "share code is too short"),
+            ShareCodeError::TooLong => write!(f, "share code is too long"),
+            ShareCodeError::InvalidPadding => write!(f, "share code ends with unexpected bits"),
+            ShareCodeError::UnknownPreset(idx) => write!(f, "unknown preset #{}", idx),
+            ShareCodeError::InvalidMonth(idx) => write!(f, "invalid month #{}", idx),
+            ShareCodeError::InvalidWeekday(idx) => write!(f, "invalid weekday #{}", idx),
+            ShareCodeError::InvalidDate(target) => write!(f, "{} {} {} can't be shown on the board", target.day_of_week, target.month, target.day_of_month),
+            ShareCodeError::InvalidPlacement(piece_idx) => write!(f, "piece {} is placed off the board", piece_idx),
+            ShareCodeError::Conflict(piece_idx) => write!(f, "piece {} overlaps another piece or the date", piece_idx),
+        }
+    }
+}
+
+impl core::error::Error for ShareCodeError {}
+
+fn preset_idx(preset: Preset) -> usize {
+    Preset::ALL.iter().position(|p| *p == preset).expect("preset missing from Preset::ALL")
+}
+
+#[derive(Default)]
+struct BitWriter {
+    out: String,
+    buffer: u32,
+    num_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        for bit_idx in (0..bits).rev() {
+            self.buffer = (self.buffer << 1) | ((value >> bit_idx) & 1);
+            self.num_bits += 1;
+            if self.num_bits == 6 {
+                self.flush();
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        self.out.push(ALPHABET[self.buffer as usize] as char);
+        self.buffer = 0;
+        self.num_bits = 0;
+    }
+
+    fn finish(mut self) -> String {
+        if self.num_bits > 0 {
+            self.buffer <<= 6 - self.num_bits;
+            self.flush();
+        }
+
+        self.out
+    }
+}
+
+struct BitReader {
+    sextets: Vec<u8>,
+    bit_idx: usize,
+}
+
+impl BitReader {
+    fn create(code: &str) -> Result<Self, ShareCodeError> {
+        let sextets = code.chars()
+            .map(|c| ALPHABET.iter()
+                .position(|a| *a as char == c)
+                .map(|idx| idx as u8)
+                .ok_or(ShareCodeError::InvalidCharacter(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { sextets, bit_idx: 0 })
+    }
+
+    fn read(&mut self, bits: u32) -> Result<u32, ShareCodeError> {
+        let mut out = 0;
+        for _ in 0..bits {
+            let sextet = *self.sextets.get(self.bit_idx / 6).ok_or(ShareCodeError::TooShort)?;
+            let bit = (sextet >> (5 - (self.bit_idx % 6))) & 1;
+            out = (out << 1) | bit as u32;
+            self.bit_idx += 1;
+        }
+
+        Ok(out)
+    }
+
+    // everything after the last field is padding, which must fit in the last character and be all zeros
+    fn finish(mut self) -> Result<(), ShareCodeError> {
+        let padding_bits = self.sextets.len() * 6 - self.bit_idx;
+        if padding_bits >= 6 {
+            Err(ShareCodeError::TooLong)
+        } else if self.read(padding_bits as u32)? != 0 {
+            Err(ShareCodeError::InvalidPadding)
+        } else {
+            Ok(())
+        }
+    }
+}
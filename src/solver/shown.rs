@@ -0,0 +1,98 @@
+use super::prelude::*;
+
+use core::fmt;
+
+/// Why the cells left uncovered on a board don't show a date.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ShownDateError {
+    MissingMonth,
+    MissingDay,
+    /// No weekday is uncovered, on a board which shows the weekday.
+    MissingWeekday,
+    /// More than one month is uncovered; these are the first two, row by row.
+    MultipleMonths(Month, Month),
+    MultipleDays(i8, i8),
+    MultipleWeekdays(Weekday, Weekday),
+    /// A cell which isn't part of a date is uncovered: a blank cell, or a weekday on a board which doesn't show the
+    /// weekday.
+    ExtraCell { x: usize, y: usize },
+    /// The month doesn't have the day, even in a leap year.
+    InvalidDay { month: Month, day: i8 },
+}
+
+impl fmt::Display for ShownDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShownDateError::MissingMonth => write!(f, "no month is uncovered"),
+            ShownDateError::MissingDay => write!(f, "no day of the month is uncovered"),
+            ShownDateError::MissingWeekday => write!(f, "no day of the week is uncovered"),
+            ShownDateError::MultipleMonths(a, b) => write!(f, "both {} and {} are uncovered", a, b),
+            ShownDateError::MultipleDays(a, b) => write!(f, "both day {} and day {} are uncovered", a, b),
+            ShownDateError::MultipleWeekdays(a, b) => write!(f, "both {} and {} are uncovered", a, b),
+            ShownDateError::ExtraCell { x, y } => write!(f, "the cell at ({}, {}) is uncovered, but isn't part of a date", x, y),
+            ShownDateError::InvalidDay { month, day } => write!(f, "{} has no day {}", month, day),
+        }
+    }
+}
+
+impl core::error::Error for ShownDateError {}
+
+impl Preset {
+    /// The date shown by the cells which `mask` leaves uncovered: the inverse of [`Preset::winning_mask`].
+    ///
+    /// On presets which don't show the day of the week, the weekday of the date is always Monday.
+    pub fn shown_target(&self, mask: BoardMask) -> Result<TargetDate, ShownDateError> {
+        let uses_weekday = self.semantics().uses_weekday();
+        let mut month = None;
+        let mut day = None;
+        let mut weekday = None;
+
+        for (x, y) in iter_coordinates().filter(|(x, y)| !mask.is_covered(*x, *y)) {
+            match self.labels()[y][x] {
+                BoardLabel::MonthLabel(shown) => set_once(&mut month, shown, ShownDateError::MultipleMonths)?,
+                BoardLabel::DayLabel(shown) => set_once(&mut day, shown, ShownDateError::MultipleDays)?,
+                BoardLabel::DayOfWeekLabel(shown) if uses_weekday => set_once(&mut weekday, shown, ShownDateError::MultipleWeekdays)?,
+                _ => return Err(ShownDateError::ExtraCell { x, y }),
+            }
+        }
+
+        let month = month.ok_or(ShownDateError::MissingMonth)?;
+        let day_of_month = day.ok_or(ShownDateError::MissingDay)?;
+        let day_of_week = match weekday {
+            Some(weekday) => weekday,
+            None if uses_weekday => return Err(ShownDateError::MissingWeekday),
+            None => Weekday::Monday,
+        };
+
+        if day_of_month > month.number_days(true) {
+            return Err(ShownDateError::InvalidDay { month, day: day_of_month });
+        }
+
+        Ok(TargetDate { month, day_of_month, day_of_week })
+    }
+}
+
+impl GameState {
+    /// The date shown by the cells which the pieces leave uncovered, see [`Preset::shown_target`].
+    pub fn shown_target(&self) -> Result<TargetDate, ShownDateError> {
+        self.preset().shown_target(self.mask())
+    }
+}
+
+impl TaggedMask {
+    /// The date shown by the cells which aren't covered by a piece on the board of `preset`, see
+    /// [`Preset::shown_target`].
+    pub fn shown_target(&self, preset: Preset) -> Result<TargetDate, ShownDateError> {
+        preset.shown_target(BoardMask::from(*self))
+    }
+}
+
+fn set_once<T: Copy>(slot: &mut Option<T>, value: T, err: fn(T, T) -> ShownDateError) -> Result<(), ShownDateError> {
+    match *slot {
+        Some(first) => Err(err(first, value)),
+        None => {
+            *slot = Some(value);
+            Ok(())
+        }
+    }
+}
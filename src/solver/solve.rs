@@ -1,42 +1,286 @@
 use super::prelude::*;
 
-use std::fmt;
+use core::fmt;
+use alloc::collections::BTreeSet;
+#[cfg(feature="timed")]
 use std::time::{Duration, Instant};
 use crate::return_matching;
 
+// the mask is checked against the pieces when deserializing
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "SolutionRepr"))]
 pub struct Solution {
     pub mask: TaggedMask,
     pub game: GameState,
     pub steps: usize,
     #[cfg(feature="timed")]
     pub duration: Duration,
+    /// How the solver used its memo of dead boards, if it was created by [`solve_memoized`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub memo: Option<MemoStats>,
 }
 
 pub fn solve(target: TargetDate) -> impl Iterator<Item=SolverMsg> {
-    target.winning_mask().into_iter().flat_map(move |winning_mask| Solver::create(winning_mask))
+    solve_preset(Preset::default(), target)
 }
 
-struct Solver {
+/// Solves the classic preset for anything which converts into a target date, like a [`CalendarDate`] (or, with the
+/// `chrono` and `time` features, a `chrono::NaiveDate` or `time::Date`).
+pub fn solve_for(date: impl Into<TargetDate>) -> impl Iterator<Item=SolverMsg> {
+    solve(date.into())
+}
+
+pub fn solve_preset(preset: Preset, target: TargetDate) -> impl Iterator<Item=SolverMsg> {
+    preset.winning_mask(target).into_iter().flat_map(move |winning_mask| Solver::create(preset, winning_mask))
+}
+
+/// Solves `target` starting from a previous solution of the same preset, such as the one for the day before.
+///
+/// Only the pieces which cover the cells showing `target` are taken off, and the solver first looks for a way to place
+/// them around the pieces which stay. If there is none, it also takes off the pieces touching the cells which changed
+/// and searches again with the others fixed, and only if that fails too does it start over from an empty board. The
+/// steps of every search are counted, and [`Solution::moved_pieces`] tells how much of `previous` was kept.
+///
+/// On the classic board, the solutions for one day and the next usually have only a piece or two in common, so this
+/// helps the most when only one cell of the date moves.
+pub fn solve_from(previous: &Solution, target: TargetDate) -> impl Iterator<Item=SolverMsg> {
+    let previous = previous.game;
+    let preset = previous.preset();
+    preset.winning_mask(target).into_iter().flat_map(move |winning_mask| Solver::create_warm(preset, winning_mask, previous))
+}
+
+/// Solves like [`solve_preset`], remembering up to `capacity` boards which turned out to be dead ends. The capacity is
+/// rounded up to a power of two, of at most 2^24 boards.
+///
+/// The solver can reach the same covered cells with the same pieces left by placing pieces in a different order, and
+/// skips the boards it remembers instead of exploring them again. This takes fewer steps, at the cost of the memory for
+/// the memo. How well it worked is reported in [`Solution::memo`].
+pub fn solve_memoized(preset: Preset, target: TargetDate, capacity: usize) -> impl Iterator<Item=SolverMsg> {
+    preset.winning_mask(target)
+        .into_iter()
+        .flat_map(move |winning_mask| Solver { memo: Some(DeadStates::with_capacity(capacity)), ..Solver::create(preset, winning_mask) })
+}
+
+/// Solves for any date of the pattern in one search, which leaves open whichever cells of the pattern the pieces can be
+/// arranged around. With any weekday, for example, the solver chooses the weekday to leave open as it goes.
+///
+/// Every solution leaves a month, a day and (on boards which show it) a weekday of the pattern open, which together are
+/// a valid date. When the pieces leave more cells open than that, the others are among the pattern's
+/// [free cells](TargetPattern::with_free_cells). Use [`TargetPattern::solved_target`] to find which date was solved.
+/// Nothing is yielded when the preset can't show any date of the pattern.
+pub fn solve_pattern(preset: Preset, pattern: TargetPattern) -> impl Iterator<Item=SolverMsg> {
+    pattern.candidates(preset).next().into_iter().flat_map(move |_| Solver::create_pattern(preset, preset.pieces(), pattern))
+}
+
+/// Like [`solve_pattern`], with any `pieces` on the board of `preset`, as in [`solve_pieces`]. Pieces which cover fewer
+/// cells than the board has besides the date leave some of the pattern's free cells open.
+pub fn solve_pattern_pieces(preset: Preset, pieces: &[Piece], pattern: TargetPattern) -> impl Iterator<Item=PieceSolverMsg> + '_ {
+    pattern.candidates(preset).next().into_iter()
+        .flat_map(move |_| piece_solver_msgs(pieces, move || Solver::create_pattern(preset, pieces, pattern)))
+}
+
+/// Finds every distinct solution for `target`, in the order the solver discovers them.
+///
+/// Solutions which only differ in how equal placements are expressed (see [`CanonicalSolution`]) are yielded once.
+pub fn solve_all(preset: Preset, target: TargetDate) -> impl Iterator<Item=Solution> {
+    let mut seen = BTreeSet::new();
+    preset.winning_mask(target)
+        .into_iter()
+        .flat_map(move |winning_mask| Solver::create_exhaustive(preset, winning_mask))
+        .filter_map(|msg| match msg {
+            SolverMsg::Solved(solution) => Some(solution),
+            _ => None,
+        })
+        .filter(move |solution| seen.insert(solution.canonical()))
+}
+
+/// Covers every cell of `winning_mask` with `pieces`, which can be any shapes, like ones [parsed](Shape) from text and
+/// made into pieces with [`Piece::new`]. Every piece is used once, and the cells the mask leaves open stay open.
+///
+/// This is the solver behind [`solve_preset`], without the preset: the steps only have the cells the pieces cover,
+/// which are tagged with the index of each piece in `pieces`. There can be at most [`MAX_PIECES`] pieces, and more
+/// are [`PieceSolverMsg::Impossible`].
+pub fn solve_pieces(pieces: &[Piece], winning_mask: BoardMask) -> impl Iterator<Item=PieceSolverMsg> + '_ {
+    piece_solver_msgs(pieces, move || Solver::create_with(pieces, GameState::default(), winning_mask))
+}
+
+// the steps of a solver for `pieces`, or impossible when there are too many of them
+fn piece_solver_msgs<'a>(pieces: &'a [Piece], create: impl FnOnce() -> Solver<'a>) -> impl Iterator<Item=PieceSolverMsg> + 'a {
+    let solver = (pieces.len() <= MAX_PIECES).then(create);
+    let too_many = solver.is_none().then_some(PieceSolverMsg::Impossible);
+    solver.into_iter().flatten()
+        .map(move |msg| match msg {
+            SolverMsg::Unsolved(_, mask) => PieceSolverMsg::Unsolved(mask),
+            SolverMsg::Solved(solution) => PieceSolverMsg::Solved(PieceSolution {
+                placements: solution.game.all_placements()[..pieces.len()].iter().flatten().copied().collect(),
+                mask: solution.mask,
+                steps: solution.steps,
+            }),
+            SolverMsg::Impossible => PieceSolverMsg::Impossible,
+        })
+        .chain(too_many)
+}
+
+/// A step of [`solve_pieces`], like a [`SolverMsg`] for pieces which aren't a preset's.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PieceSolverMsg {
+    Unsolved(TaggedMask),
+    Solved(PieceSolution),
+    Impossible,
+}
+
+/// How [`solve_pieces`] covered the board.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PieceSolution {
+    /// The placement of each piece, in the order they were given.
+    pub placements: Vec<Placement>,
+    pub mask: TaggedMask,
+    pub steps: usize,
+}
+
+struct Solver<'a> {
+    // the preset's pieces, or any others. With other pieces, the preset of the boards is meaningless
+    pieces: &'a [Piece],
+    // the cells the pieces may cover. The others are open from the start
     winning_mask: BoardMask,
+    // when solving a pattern, which of the cells the pieces may cover can be left open instead
+    goal: Option<PatternGoal>,
+    // the board the search starts from, which is empty unless warm starting, and the boards to start from when that
+    // search fails
+    start: GameState,
+    fallbacks: Vec<GameState>,
+    // when set, the solver keeps searching after a solution, and simply ends once every option is exhausted
+    exhaustive: bool,
+    // boards known to be dead ends, which only hold for a solver which stops at its first solution
+    memo: Option<DeadStates>,
     stats: SolverStats,
-    frames: Option<Vec<SolveFrame>>,
+    frames: Option<Vec<SolveFrame<'a>>>,
+}
+
+impl Solver<'static> {
+    pub fn create(preset: Preset, winning_mask: BoardMask) -> Self {
+        Solver::create_with(preset.pieces(), GameState::new(preset), winning_mask)
+    }
+
+    pub fn create_exhaustive(preset: Preset, winning_mask: BoardMask) -> Self {
+        Self { exhaustive: true, ..Self::create(preset, winning_mask) }
+    }
+
+    // starts with the pieces of `previous` which can stay where they are. When that fails, the pieces touching the cells
+    // which changed are taken off too, and only when that also fails does the search start over from an empty board
+    pub fn create_warm(preset: Preset, winning_mask: BoardMask, previous: GameState) -> Self {
+        let mut start = GameState::new(preset);
+        for (piece_idx, placement) in previous.placements().iter().copied().enumerate() {
+            if placement.is_some() {
+                // this refuses pieces which cover the target, or overlap a piece placed before them
+                start.place_piece(piece_idx, placement, winning_mask);
+            }
+        }
+
+        let changed = iter_coordinates()
+            .filter(|(x, y)| previous.mask().is_covered(*x, *y) != winning_mask.is_covered(*x, *y))
+            .collect::<Vec<_>>();
+        let touches_changed = |piece_idx: usize| {
+            let cells = start.placements()[piece_idx].and_then(|placement| preset.piece_mask(piece_idx, &placement)).unwrap_or_default();
+            iter_coordinates()
+                .filter(|(x, y)| cells.is_covered(*x, *y))
+                .any(|(x, y)| changed.iter().any(|(cx, cy)| x.abs_diff(*cx) + y.abs_diff(*cy) <= 1))
+        };
+        let mut untouched = start;
+        for piece_idx in 0..preset.num_pieces() {
+            if start.placements()[piece_idx].is_some() && touches_changed(piece_idx) {
+                untouched.place_piece(piece_idx, None, winning_mask);
+            }
+        }
+
+        // these are popped, so the last start is tried first
+        let mut fallbacks = Vec::with_capacity(2);
+        for fallback in [GameState::new(preset), untouched] {
+            if fallback != start && fallbacks.last() != Some(&fallback) {
+                fallbacks.push(fallback);
+            }
+        }
+
+        Self { start, fallbacks, ..Self::create(preset, winning_mask) }
+    }
 }
 
-impl Solver {
-    pub fn create(winning_mask: BoardMask) -> Self {
+impl<'a> Solver<'a> {
+    fn create_with(pieces: &'a [Piece], start: GameState, winning_mask: BoardMask) -> Self {
         Self {
+            pieces,
             winning_mask,
+            goal: None,
+            start,
+            fallbacks: Vec::new(),
+            exhaustive: false,
+            memo: None,
             stats: SolverStats {
                 steps: 0,
                 #[cfg(feature="timed")]
                 start_at: Instant::now(),
             },
-            // this capacity of NUM_PIECES is because we actually can only have one frame per placed piece.
-            // that is because we only create a frame after placing a piece.
-            frames: Some(Vec::with_capacity(NUM_PIECES)),
+            // this capacity of MAX_PIECES is because we actually can only have one frame per placed piece.
+            // that is because we only create a frame after placing a piece (or, for a pattern, leaving a cell open).
+            frames: Some(Vec::with_capacity(MAX_PIECES)),
+        }
+    }
+
+    // any cell of the pattern may be covered, or left open if that still leads to a date of the pattern
+    fn create_pattern(preset: Preset, pieces: &'a [Piece], pattern: TargetPattern) -> Self {
+        let goal = PatternGoal {
+            preset,
+            pattern,
+            optional: pattern.open_cells(preset),
+            num_open: (PUZZLE_WIDTH * PUZZLE_HEIGHT).saturating_sub(pieces.iter().map(Piece::area).sum()),
+        };
+        Self { goal: Some(goal), ..Self::create_with(pieces, GameState::new(preset), BoardMask::filled()) }
+    }
+}
+
+// what solving a pattern asks of the cells which are left open
+struct PatternGoal {
+    preset: Preset,
+    pattern: TargetPattern,
+    // the cells which may be left open, and how many are once every piece is placed
+    optional: BoardMask,
+    num_open: usize,
+}
+
+impl PatternGoal {
+    fn can_leave_open(&self, open: BoardMask, x: usize, y: usize) -> bool {
+        self.optional.is_covered(x, y) && open.count() < self.num_open && self.pattern.can_leave_open(self.preset, open, x, y)
+    }
+
+    // leaves the first undecided cells open for as long as they can't be covered, like the cells of the date when
+    // solving for a single one. Returns whether the board can still be finished with a date of the pattern open
+    fn settle(&self, state: GameState, open: &mut BoardMask) -> bool {
+        loop {
+            let mut decided = state.mask();
+            decided.apply(*open);
+            let Some((x, y)) = iter_coordinates().find(|(x, y)| !decided.is_covered(*x, *y)) else {
+                return self.pattern.shown_target(self.preset, open.inverted()).is_some();
+            };
+
+            let mut undecided = self.optional.without(decided);
+            if !self.can_finish(*open, undecided) {
+                return false;
+            }
+            undecided.set_covered(x, y, false);
+            if self.can_finish(*open, undecided) {
+                return true;
+            }
+            if !self.can_leave_open(*open, x, y) {
+                return false;
+            }
+            open.set_covered(x, y, true);
         }
     }
+
+    // whether enough of the `undecided` cells can be left open to show a date of the pattern
+    fn can_finish(&self, open: BoardMask, undecided: BoardMask) -> bool {
+        self.pattern.can_open(self.preset, open, undecided).is_some_and(|more| open.count() + more >= self.num_open)
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -46,6 +290,23 @@ pub enum SolverMsg {
     Impossible
 }
 
+impl Solution {
+    pub fn canonical(&self) -> CanonicalSolution {
+        self.game.canonical()
+    }
+
+    /// The `memo` field, from before it was public.
+    pub fn memo(&self) -> Option<MemoStats> {
+        self.memo
+    }
+
+    /// How many pieces cover different cells than they do in `previous`.
+    pub fn moved_pieces(&self, previous: &Solution) -> usize {
+        let (current, previous) = (self.canonical(), previous.canonical());
+        (0..MAX_PIECES).filter(|piece_idx| current.piece_cells(*piece_idx) != previous.piece_cells(*piece_idx)).count()
+    }
+}
+
 impl fmt::Display for SolverMsg {
 
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -65,7 +326,7 @@ impl fmt::Debug for SolverMsg {
     }
 }
 
-impl Iterator for Solver {
+impl Iterator for Solver<'_> {
     type Item = SolverMsg;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -79,11 +340,11 @@ impl Iterator for Solver {
         // next_state = the next step of solving that we plan to return
         //
         // the initial state of this type Solver is `frames = Some(Vec::default())` essentially,
-        // so when we encounter that first state, we initialize a default (empty) board as our "next_state"
+        // so when we encounter that first state, we start from the `start` board (which is usually empty)
         //
         // otherwise... check the else branch
-        let next_state = if frames.is_empty() {
-            GameState::default()
+        let (next_state, next_open) = if frames.is_empty() {
+            (self.start, self.winning_mask.inverted())
         } else {
             // this is the other state of solver... non-default state, where we've made some progress, and want to
             // continue solving
@@ -94,63 +355,114 @@ impl Iterator for Solver {
                 let num_frames = frames.len();
                 let current_frame = &mut frames[num_frames - 1];
 
-                // go through the iterator `piece_placements` to find the next valid move to make in this frame
-                for (piece_idx, placement) in &mut current_frame.piece_placements {
+                // go through the iterator `moves` to find the next valid move to make in this frame
+                for next_move in &mut current_frame.moves {
                     // if we find a move, and can make it, then that is our next state!
-                    let mut next_state = current_frame.state;
-                    if next_state.place_piece(piece_idx, Some(placement), self.winning_mask) {
-                        break 'l next_state;
+                    let (mut next_state, mut next_open) = (current_frame.state, current_frame.open);
+                    match next_move {
+                        SolveMove::Place(piece_idx, placement, piece_mask) => {
+                            if !next_state.add_piece(piece_idx, placement, piece_mask, next_open.inverted()) {
+                                continue;
+                            }
+                        }
+                        SolveMove::LeaveOpen(x, y) => next_open.set_covered(x, y, true),
                     }
+
+                    // unless we already know there is no way to finish this board
+                    if self.memo.as_mut().is_some_and(|memo| memo.contains(&next_state)) {
+                        continue;
+                    }
+                    // or it can't show a date of the pattern anymore
+                    if self.goal.as_ref().is_some_and(|goal| !goal.settle(next_state, &mut next_open)) {
+                        continue;
+                    }
+                    break 'l (next_state, next_open);
                 }
 
                 // if we never found a move to make, then this frame is impossible, so we should remove it
                 // due to this being in a `loop` called 'l, this will cause us to simply move up one frame
-                frames.pop();
+                if let (Some(dead_frame), Some(memo)) = (frames.pop(), self.memo.as_mut()) {
+                    memo.insert(&dead_frame.state);
+                }
 
                 // if that was the last frame, then technically the entire puzzle is impossible, so we completely fuse
+                //
+                // an exhaustive solver has no more solutions to find at this point, so it just ends
                 if frames.is_empty() {
+                    // a warm start which couldn't be completed tries again with fewer pieces on the board
+                    if let Some(start) = self.fallbacks.pop() {
+                        break 'l (start, self.winning_mask.inverted());
+                    }
+
                     self.frames.take();
-                    return Some(SolverMsg::Impossible);
+                    return if self.exhaustive { None } else { Some(SolverMsg::Impossible) };
                 }
             }
         };
 
         // we either solved the puzzle or we didn't
-        Some(if next_state.mask() == self.winning_mask {
+        Some(if is_decided(next_state, next_open) {
             // if we solve the puzzle, just return immediately
-            self.frames.take();
+            //
+            // an exhaustive solver keeps its frames, so the next call carries on with the next placement
+            if !self.exhaustive {
+                self.frames.take();
+            }
             SolverMsg::Solved(Solution {
                 game: next_state,
-                mask: next_state.tagged_mask(self.winning_mask),
+                mask: next_state.tagged_mask_of(self.pieces, next_open.inverted()),
                 steps: self.stats.steps,
                 #[cfg(feature="timed")]
                 duration: Instant::now() - self.stats.start_at,
+                memo: self.memo.as_ref().map(DeadStates::stats),
             })
         } else {
             // otherwise, push a new frame
-            frames.push(SolveFrame::create(next_state, self.winning_mask));
+            let goal = self.goal.as_ref();
+            let can_leave_open = |x, y| goal.is_some_and(|goal| goal.can_leave_open(next_open, x, y));
+            frames.push(SolveFrame::create(next_state, next_open, self.pieces, can_leave_open));
             self.stats.steps += 1;
-            SolverMsg::Unsolved(next_state, next_state.tagged_mask(self.winning_mask))
+            SolverMsg::Unsolved(next_state, next_state.tagged_mask_of(self.pieces, next_open.inverted()))
         })
     }
 }
 
-struct SolveFrame {
+// every cell is either covered, or open for good
+fn is_decided(state: GameState, open: BoardMask) -> bool {
+    let mut decided = state.mask();
+    decided.apply(open);
+    decided == BoardMask::filled()
+}
+
+struct SolveFrame<'a> {
     state: GameState,
-    piece_placements: Box<dyn Iterator<Item=(usize, Placement)>>
+    // the cells which stay open
+    open: BoardMask,
+    // leaving the first undecided cell open if allowed, then each piece which isn't on the board yet, placed in each
+    // way which covers that cell
+    moves: Box<dyn Iterator<Item=SolveMove> + 'a>
 }
 
-impl SolveFrame {
-    fn create(state: GameState, winning_mask: BoardMask) -> Self {
-        let piece_placements = Box::new(
-            state.open_positions(winning_mask)
-                .next()
-                .into_iter()
+enum SolveMove {
+    Place(usize, Placement, BoardMask),
+    LeaveOpen(usize, usize),
+}
+
+impl<'a> SolveFrame<'a> {
+    fn create(state: GameState, open: BoardMask, pieces: &'a [Piece], can_leave_open: impl FnOnce(usize, usize) -> bool) -> Self {
+        let mut decided = state.mask();
+        decided.apply(open);
+        let cell = iter_coordinates().find(|(x, y)| !decided.is_covered(*x, *y));
+        let leave_open = cell.filter(|(x, y)| can_leave_open(*x, *y)).map(|(x, y)| SolveMove::LeaveOpen(x, y));
+        let moves = Box::new(
+            leave_open.into_iter().chain(cell.into_iter()
                 .flat_map(move |(x, y)|
-                    state.available_piece_idxes()
-                        .flat_map(move |piece_idx| Placement::iter_covering_coordinates(x, y, piece_idx)
-                            .map(move |placement| (piece_idx, placement)))));
-        Self { state, piece_placements }
+                    pieces.iter()
+                        .enumerate()
+                        .filter(move |(piece_idx, _)| state.all_placements()[*piece_idx].is_none())
+                        .flat_map(move |(piece_idx, piece)| Placement::iter_covering_coordinates(x as u8, y as u8, piece)
+                            .filter_map(move |placement| Some(SolveMove::Place(piece_idx, placement, piece.mask(&placement)?)))))));
+        Self { state, open, moves }
     }
 }
 
@@ -158,4 +470,37 @@ struct SolverStats {
     steps: usize,
     #[cfg(feature="timed")]
     start_at: Instant,
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SolutionRepr {
+    mask: TaggedMask,
+    game: GameState,
+    steps: usize,
+    #[cfg(feature="timed")]
+    duration: Duration,
+    #[serde(default)]
+    memo: Option<MemoStats>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SolutionRepr> for Solution {
+    type Error = String;
+
+    fn try_from(value: SolutionRepr) -> Result<Self, Self::Error> {
+        // every cell of a solution is either covered or shows the date
+        if value.mask != value.game.tagged_mask(value.game.mask()) {
+            return Err("the mask doesn't match the placed pieces".to_string());
+        }
+
+        Ok(Solution {
+            mask: value.mask,
+            game: value.game,
+            steps: value.steps,
+            #[cfg(feature="timed")]
+            duration: value.duration,
+            memo: value.memo,
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use super::prelude::*;
-use std::cmp::{min, max};
+use core::cmp::{min, max};
+use core::fmt;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Placement {
     pub x: u8,
     pub y: u8,
@@ -30,6 +32,20 @@ impl Placement {
         Some(pos_code << 3 | (rotation << 1) | flipped)
     }
 
+    pub fn from_code(code: usize) -> Option<Placement> {
+        let pos_code = code >> 3;
+        if pos_code >= PUZZLE_WIDTH * PUZZLE_HEIGHT {
+            return None;
+        }
+
+        Some(Placement {
+            x: (pos_code % PUZZLE_WIDTH) as u8,
+            y: (pos_code / PUZZLE_WIDTH) as u8,
+            rotation: ((code >> 1) & 0b11) as u8,
+            flipped: code & 1 == 1,
+        })
+    }
+
     pub fn iter_all() -> impl Iterator<Item=Placement> {
         iter_coordinates().flat_map(move |(x, y)|
             (0..4).flat_map(move |rotation|
@@ -40,41 +56,61 @@ impl Placement {
     pub fn iter_covering_coordinates(
         x: u8,
         y: u8,
-        piece_idx: usize,
-    ) -> impl Iterator<Item=Placement>
+        piece: &Piece,
+    ) -> impl Iterator<Item=Placement> + '_
     {
-        piece(piece_idx).into_iter().flat_map(move |piece|
-            (0..4).flat_map(move |rotation| {
-                let (width, height) = piece.size(rotation);
-                let start_x = max(0isize, (x as isize) - (width as isize)) as u8;
-                let end_x = min(PUZZLE_WIDTH as u8, x + 1);
-                let start_y = max(0isize, (y as isize) - (height as isize)) as u8;
-                let end_y = min(PUZZLE_HEIGHT as u8, y + 1);
-
-                (start_y..end_y).flat_map(move |y|
-                    (start_x..end_x).flat_map(move |x|
-                        [false, true].map(move | flipped| Placement{x, y, rotation, flipped})))
-                    .filter(move |placement|
-                        piece.mask(placement)
-                            .map(|mask| mask.is_covered(x as usize, y as usize))
-                            .unwrap_or(false))
-            }))
+        (0..4).flat_map(move |rotation| {
+            let (width, height) = piece.size(rotation);
+            let start_x = max(0isize, (x as isize) - (width as isize)) as u8;
+            let end_x = min(PUZZLE_WIDTH as u8, x + 1);
+            let start_y = max(0isize, (y as isize) - (height as isize)) as u8;
+            let end_y = min(PUZZLE_HEIGHT as u8, y + 1);
+
+            (start_y..end_y).flat_map(move |y|
+                (start_x..end_x).flat_map(move |x|
+                    [false, true].map(move | flipped| Placement{x, y, rotation, flipped})))
+                .filter(move |placement|
+                    piece.mask(placement)
+                        .map(|mask| mask.is_covered(x as usize, y as usize))
+                        .unwrap_or(false))
+        })
     }
 }
 
+// serialized as the preset and its pieces only, the mask is computed (and the placements checked) when deserializing
 #[derive(Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "GameStateRepr", try_from = "GameStateRepr"))]
 pub struct GameState {
-    pieces: [Option<Placement>; NUM_PIECES],
+    preset: Preset,
+    pieces: [Option<Placement>; MAX_PIECES],
     mask: BoardMask,
 }
 
 impl GameState {
+    pub fn new(preset: Preset) -> Self {
+        Self { preset, ..Self::default() }
+    }
+
+    pub fn preset(&self) -> Preset {
+        self.preset
+    }
+
+    /// The placement of each of the preset's pieces, or `None` for the pieces which aren't on the board.
+    pub fn placements(&self) -> &[Option<Placement>] {
+        &self.pieces[..self.preset.num_pieces()]
+    }
+
+    /// The placements of the first [`NUM_PIECES`] pieces, which is all of them on the classic board.
+    #[deprecated(note = "this leaves out the tenth piece of the presets which have one, use `GameState::placements` instead")]
+    #[allow(deprecated)]
     pub fn pieces(&self) -> [Option<Placement>; NUM_PIECES] {
-        self.pieces
+        let mut out = [None; NUM_PIECES];
+        out.copy_from_slice(&self.pieces[..NUM_PIECES]);
+        out
     }
 
     pub fn place_piece(&mut self, piece_idx: usize, placement: Option<Placement>, winning_mask: BoardMask) -> bool {
-        if piece_idx >= NUM_PIECES {
+        if piece_idx >= self.preset.num_pieces() {
             return false;
         }
 
@@ -87,7 +123,7 @@ impl GameState {
         if let Some(new_placement) = placement {
             // Some if piece_idx is valid and placement is on the board (valid)
             // None otherwise (therefore do not process the update)
-            if let Some(mask_update) = mask_for_piece(piece_idx, &new_placement) {
+            if let Some(mask_update) = self.preset.piece_mask(piece_idx, &new_placement) {
                 let last_value = &self.pieces[piece_idx];
 
                 // what is our current board mask, without this piece placed anywhere?
@@ -100,7 +136,7 @@ impl GameState {
                     // be calculated to check for conflicts
                     let mut new_pieces = self.pieces;
                     new_pieces[piece_idx] = None;
-                    BoardMask::compute(&new_pieces)
+                    BoardMask::compute(self.preset, &new_pieces)
                 };
 
                 if !mask_update.conflicts_with(own_mask) && !mask_update.covers_winning_mask(winning_mask) {
@@ -112,18 +148,68 @@ impl GameState {
             }
         } else if self.pieces[piece_idx].is_some() { // this is also if placement == None
             self.pieces[piece_idx] = None;
-            self.mask = BoardMask::compute(&self.pieces);
+            self.mask = BoardMask::compute(self.preset, &self.pieces);
             return true;
         }
 
         false
     }
 
+    // places a piece which isn't on the board yet, for the solver, which may not be using the preset's pieces
+    pub(super) fn add_piece(&mut self, piece_idx: usize, placement: Placement, piece_mask: BoardMask, winning_mask: BoardMask) -> bool {
+        if self.pieces[piece_idx].is_some() || piece_mask.conflicts_with(self.mask) || piece_mask.covers_winning_mask(winning_mask) {
+            return false;
+        }
+
+        self.pieces[piece_idx] = Some(placement);
+        self.mask.apply(piece_mask);
+        true
+    }
+
+    // every slot, including those past the preset's pieces
+    pub(super) fn all_placements(&self) -> &[Option<Placement>; MAX_PIECES] {
+        &self.pieces
+    }
+
     pub fn mask(&self) -> BoardMask {
         self.mask
     }
 
+    /// Finds a placement for each piece on a tagged mask, such as one parsed from text.
+    ///
+    /// The cells covered by each piece must have the shape of that piece of the preset. Pieces which don't appear on
+    /// the mask aren't placed. Where a piece looks the same in several orientations, any matching placement is used.
+    pub fn from_tagged_mask(preset: Preset, mask: &TaggedMask) -> Result<Self, ReconstructError> {
+        let mut piece_masks = [BoardMask::default(); MAX_PIECES];
+        for (x, y) in iter_coordinates() {
+            if let CellTag::Covered(piece_idx) = mask.get(x, y) {
+                if piece_idx as usize >= preset.num_pieces() {
+                    return Err(ReconstructError::UnknownPiece(piece_idx));
+                }
+                piece_masks[piece_idx as usize].set_covered(x, y, true);
+            }
+        }
+
+        let mut out = Self::new(preset);
+        for (piece_idx, piece_mask) in piece_masks.iter().enumerate().take(preset.num_pieces()) {
+            if piece_mask.count() == 0 {
+                continue;
+            }
+
+            out.pieces[piece_idx] = Some(Placement::iter_all()
+                .find(|placement| preset.piece_mask(piece_idx, placement) == Some(*piece_mask))
+                .ok_or(ReconstructError::ShapeMismatch(piece_idx as u8))?);
+            out.mask.apply(*piece_mask);
+        }
+
+        Ok(out)
+    }
+
     pub fn tagged_mask(&self, winning_mask: BoardMask) -> TaggedMask {
+        self.tagged_mask_of(self.preset.pieces(), winning_mask)
+    }
+
+    pub(super) fn tagged_mask_of(&self, pieces: &[Piece], winning_mask: BoardMask) -> TaggedMask {
         let mut out = TaggedMask::default();
         for (x, y) in iter_coordinates() {
             if !winning_mask.is_covered(x, y) {
@@ -132,7 +218,7 @@ impl GameState {
         }
         for (piece_idx, placement) in self.pieces.iter().enumerate() {
             if let Some(placement) = placement {
-                if let Some(mask) = mask_for_piece(piece_idx, placement) {
+                if let Some(mask) = pieces.get(piece_idx).and_then(|piece| piece.mask(placement)) {
                     for (x, y) in iter_coordinates() {
                         if mask.is_covered(x, y) {
                             out.set(x, y, CellTag::Covered(piece_idx as u8));
@@ -158,7 +244,64 @@ impl GameState {
     }
 
     pub fn available_piece_idxes(self) -> impl Iterator<Item=usize> {
-        (0..NUM_PIECES).filter_map(move |idx|
-            if self.pieces[idx].is_none() { Some(idx) } else { None })
+        (0..self.preset.num_pieces()).filter(move |idx| self.pieces[*idx].is_none())
+    }
+}
+
+/// Why a [`GameState`] could not be found for a [`TaggedMask`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ReconstructError {
+    /// The preset has no piece with this index.
+    UnknownPiece(u8),
+    /// The cells covered by the piece don't have its shape.
+    ShapeMismatch(u8),
+}
+
+impl fmt::Display for ReconstructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReconstructError::UnknownPiece(piece_idx) => write!(f, "there is no piece {}", piece_idx),
+            ReconstructError::ShapeMismatch(piece_idx) => write!(f, "the cells of piece {} don't match its shape", piece_idx),
+        }
+    }
+}
+
+impl core::error::Error for ReconstructError {}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GameStateRepr {
+    preset: Preset,
+    pieces: Vec<Option<Placement>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<GameState> for GameStateRepr {
+    fn from(value: GameState) -> Self {
+        Self {
+            preset: value.preset,
+            pieces: value.pieces[..value.preset.num_pieces()].to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GameStateRepr> for GameState {
+    type Error = String;
+
+    fn try_from(value: GameStateRepr) -> Result<Self, Self::Error> {
+        let preset = value.preset;
+        if value.pieces.len() != preset.num_pieces() {
+            return Err(format!("expected {} pieces for the {} preset, got {}", preset.num_pieces(), preset, value.pieces.len()));
+        }
+
+        let mut out = GameState::new(preset);
+        for (piece_idx, placement) in value.pieces.into_iter().enumerate() {
+            if placement.is_some() && !out.place_piece(piece_idx, placement, BoardMask::filled()) {
+                return Err(format!("piece {} is off the board or overlaps another piece", piece_idx));
+            }
+        }
+
+        Ok(out)
     }
-}
\ No newline at end of file
+}
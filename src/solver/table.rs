@@ -0,0 +1,104 @@
+use super::prelude::*;
+
+//
+// The precomputed table holds the first solution the solver finds for every date of the classic preset.
+//
+// It is a flat array with one fixed size entry for every combination of month, day of the month (1 to 31) and
+// weekday, even the ones which aren't real dates:
+//
+//   [steps: u32 LE][placement code of piece 0: u16 LE] ... [placement code of piece 8: u16 LE]
+//
+// entries without a solution are filled with 0xFF.
+//
+// The table is generated by the `gen-table` binary, and embedded with the `precomputed` feature.
+//
+
+pub const TABLE_PRESET: Preset = Preset::Classic;
+
+const TABLE_PIECES: usize = 9;
+const ENTRY_SIZE: usize = 4 + (TABLE_PIECES * 2);
+const NUM_ENTRIES: usize = 12 * 31 * 7;
+
+#[cfg(feature = "precomputed")]
+static TABLE: &[u8] = include_bytes!("solutions.bin");
+
+/// Looks up the solution the solver would find for `target` (on the classic preset) without solving anything.
+#[cfg(feature = "precomputed")]
+pub fn lookup_solution(target: TargetDate) -> Option<Solution> {
+    let offset = table_idx(target)? * ENTRY_SIZE;
+    let entry = TABLE.get(offset..offset + ENTRY_SIZE)?;
+    decode_entry(target, entry)
+}
+
+/// Solves every valid date of the classic preset and encodes the solutions into a table for [`lookup_solution`].
+///
+/// `on_progress` is called with each date after it is solved.
+pub fn generate_table(mut on_progress: impl FnMut(TargetDate, Option<&Solution>)) -> Vec<u8> {
+    debug_assert_eq!(TABLE_PRESET.num_pieces(), TABLE_PIECES);
+    let mut out = vec![0xFF; NUM_ENTRIES * ENTRY_SIZE];
+    for target in TABLE_PRESET.valid_dates() {
+        let solution = match solve_preset(TABLE_PRESET, target).last() {
+            Some(SolverMsg::Solved(solution)) => Some(solution),
+            _ => None,
+        };
+
+        if let (Some(solution), Some(idx)) = (&solution, table_idx(target)) {
+            let offset = idx * ENTRY_SIZE;
+            encode_entry(solution, &mut out[offset..offset + ENTRY_SIZE]);
+        }
+
+        on_progress(target, solution.as_ref());
+    }
+
+    out
+}
+
+fn table_idx(target: TargetDate) -> Option<usize> {
+    if !(1..=31).contains(&target.day_of_month) {
+        return None;
+    }
+
+    let day_idx = (target.day_of_month - 1) as usize;
+    Some(((target.month as usize * 31) + day_idx) * 7 + target.day_of_week as usize)
+}
+
+fn encode_entry(solution: &Solution, out: &mut [u8]) {
+    out[..4].copy_from_slice(&(solution.steps as u32).to_le_bytes());
+    for (piece_idx, placement) in solution.game.placements().iter().take(TABLE_PIECES).enumerate() {
+        let code = placement.and_then(|placement| placement.code()).map(|code| code as u16).unwrap_or(u16::MAX);
+        let offset = 4 + piece_idx * 2;
+        out[offset..offset + 2].copy_from_slice(&code.to_le_bytes());
+    }
+}
+
+#[cfg_attr(not(feature = "precomputed"), allow(dead_code))]
+fn decode_entry(target: TargetDate, entry: &[u8]) -> Option<Solution> {
+    let steps = u32::from_le_bytes(entry[..4].try_into().ok()?);
+    if steps == u32::MAX {
+        return None;
+    }
+
+    let winning_mask = TABLE_PRESET.winning_mask(target)?;
+    let mut game = GameState::new(TABLE_PRESET);
+    for piece_idx in 0..TABLE_PIECES {
+        let offset = 4 + piece_idx * 2;
+        let code = u16::from_le_bytes(entry[offset..offset + 2].try_into().ok()?);
+        let placement = Placement::from_code(code as usize)?;
+        if !game.place_piece(piece_idx, Some(placement), winning_mask) {
+            return None;
+        }
+    }
+
+    if game.mask() != winning_mask {
+        return None;
+    }
+
+    Some(Solution {
+        mask: game.tagged_mask(winning_mask),
+        game,
+        steps: steps as usize,
+        #[cfg(feature="timed")]
+        duration: std::time::Duration::ZERO,
+        memo: None,
+    })
+}
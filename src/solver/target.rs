@@ -1,6 +1,7 @@
 use super::prelude::*;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TargetDate {
     pub month: Month,
     pub day_of_week: Weekday,
@@ -9,50 +10,7 @@ pub struct TargetDate {
 
 impl TargetDate {
     pub fn winning_mask(&self) -> Option<BoardMask> {
-        if self.day_of_month > 31 || self.day_of_month < 1 {
-            return None;
-        }
-
-        let mut out = BoardMask::filled();
-
-        let mut has_month = false;
-        let mut has_day_of_week = false;
-        let mut has_day = false;
-
-        for y in 0..PUZZLE_HEIGHT {
-            for x in 0..PUZZLE_WIDTH {
-                match BOARD_LABELS[y][x] {
-                    BoardLabel::MonthLabel(month) if month == self.month => {
-                        if has_month {
-                            panic!("duplicate month label for {:?}", month);
-                        }
-                        out.set_covered(x, y, false);
-                        has_month = true;
-                    }
-                    BoardLabel::DayOfWeekLabel(weekday) if weekday == self.day_of_week => {
-                        if has_day_of_week {
-                            panic!("duplicate weekday label for {:?}", weekday);
-                        }
-                        out.set_covered(x, y, false);
-                        has_day_of_week = true;
-                    }
-                    BoardLabel::DayLabel(day) if day == self.day_of_month => {
-                        if has_day {
-                            panic!("duplicate day label for {}", day);
-                        }
-                        out.set_covered(x, y, false);
-                        has_day = true;
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        if has_month && has_day_of_week && has_day {
-            Some(out)
-        } else {
-            None
-        }
+        Preset::Classic.winning_mask(*self)
     }
 
     pub fn next(&self, leap_year: bool) -> Option<Self> {
@@ -62,14 +20,16 @@ impl TargetDate {
             next.day_of_month = next_day;
             next.day_of_week = next_weekday;
             Some(next)
-        } else if let Some(next_month) = self.month.next() {
-            Some(TargetDate{month: next_month, day_of_month: 1, day_of_week: next_weekday})
         } else {
-            None
+            self.month.next().map(|next_month| TargetDate{month: next_month, day_of_month: 1, day_of_week: next_weekday})
         }
     }
 }
 
+/// Iterates the dates from a starting date through December 31st.
+///
+/// Since a target date has no year, the caller decides whether February 29th is included. To iterate across the end
+/// of a year, or backwards, see [`CalendarDateIter`].
 pub struct TargetDateIter {
     current: Option<TargetDate>,
     leap_year: bool,
@@ -0,0 +1,71 @@
+use super::prelude::*;
+
+impl From<time::Weekday> for Weekday {
+    fn from(value: time::Weekday) -> Self {
+        Weekday::ALL[value.number_days_from_monday() as usize]
+    }
+}
+
+impl From<Weekday> for time::Weekday {
+    fn from(value: Weekday) -> Self {
+        use time::Weekday as TW;
+        match value {
+            Weekday::Monday => TW::Monday,
+            Weekday::Tuesday => TW::Tuesday,
+            Weekday::Wednesday => TW::Wednesday,
+            Weekday::Thursday => TW::Thursday,
+            Weekday::Friday => TW::Friday,
+            Weekday::Saturday => TW::Saturday,
+            Weekday::Sunday => TW::Sunday,
+        }
+    }
+}
+
+impl From<time::Month> for Month {
+    fn from(value: time::Month) -> Self {
+        Month::ALL[value as usize - 1]
+    }
+}
+
+impl From<Month> for time::Month {
+    fn from(value: Month) -> Self {
+        use time::Month as TM;
+        match value {
+            Month::January => TM::January,
+            Month::February => TM::February,
+            Month::March => TM::March,
+            Month::April => TM::April,
+            Month::May => TM::May,
+            Month::June => TM::June,
+            Month::July => TM::July,
+            Month::August => TM::August,
+            Month::September => TM::September,
+            Month::October => TM::October,
+            Month::November => TM::November,
+            Month::December => TM::December,
+        }
+    }
+}
+
+impl From<time::Date> for CalendarDate {
+    fn from(value: time::Date) -> Self {
+        CalendarDate::from_ymd(value.year(), value.month().into(), value.day() as i8)
+            .expect("time dates are valid")
+    }
+}
+
+impl From<time::Date> for TargetDate {
+    fn from(value: time::Date) -> Self {
+        CalendarDate::from(value).target()
+    }
+}
+
+/// Fails for years outside of the range time supports.
+impl TryFrom<CalendarDate> for time::Date {
+    type Error = time::error::ComponentRange;
+
+    fn try_from(value: CalendarDate) -> Result<Self, Self::Error> {
+        let (year, month, day) = value.ymd();
+        time::Date::from_calendar_date(year, month.into(), day as u8)
+    }
+}
@@ -1,14 +1,19 @@
 use yew::prelude::*;
-use crate::solver::TargetDate;
+use crate::solver::{Locale, Preset, TargetDate};
+use super::dropdown::*;
 use super::picker::*;
 use super::solver::*;
 
 pub struct App {
+    preset: Preset,
+    locale: Locale,
     target: Option<TargetDate>
 }
 
 #[derive(Debug)]
 pub enum AppMsg {
+    PickPreset(Option<Preset>),
+    PickLocale(Option<Locale>),
     TargetPicked(Option<TargetDate>)
 }
 
@@ -18,6 +23,8 @@ impl Component for App {
 
     fn create(_: &Context<Self>) -> Self {
         Self {
+            preset: Preset::default(),
+            locale: Locale::default(),
             target: None
         }
     }
@@ -25,6 +32,12 @@ impl Component for App {
     fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
         log::debug!("got {:?}", msg);
         match msg {
+            AppMsg::PickPreset(preset) => {
+                self.preset = preset.unwrap_or_default();
+            }
+            AppMsg::PickLocale(locale) => {
+                self.locale = locale.unwrap_or_default();
+            }
             AppMsg::TargetPicked(target) => {
                 self.target = target;
             }
@@ -39,8 +52,22 @@ impl Component for App {
             <div class="app">
                 <h1 class="title">{"Joey's Calendar Puzzle Solver"}</h1>
                 <p class="subtext">{"Coding by Joey, style by Siqi!"}</p>
-                <Picker on_picked={ctx.link().callback(AppMsg::TargetPicked)} />
-                <SolverCmp target={self.target} />
+                <div class="preset-picker">
+                    <Dropdown<Preset>
+                        placeholder={"Edition"}
+                        values={Preset::ALL.to_vec()}
+                        on_change={ctx.link().callback(AppMsg::PickPreset)}
+                        value={Some(self.preset)}
+                        disabled={false}/>
+                    <Dropdown<Locale>
+                        placeholder={"Language"}
+                        values={Locale::ALL.to_vec()}
+                        on_change={ctx.link().callback(AppMsg::PickLocale)}
+                        value={Some(self.locale)}
+                        disabled={false}/>
+                </div>
+                <Picker preset={self.preset} locale={self.locale} on_picked={ctx.link().callback(AppMsg::TargetPicked)} />
+                <SolverCmp preset={self.preset} locale={self.locale} target={self.target} />
             </div>
         }
     }
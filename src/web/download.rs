@@ -0,0 +1,33 @@
+use web_sys::js_sys::{Array, Uint8Array};
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+// how long the browser gets to start saving the file, before its URL is revoked
+const REVOKE_DELAY_MS: i32 = 60_000;
+
+/// Has the browser save `bytes` as a file, by clicking a temporary link to them.
+pub fn download(file_name: &str, mime_type: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    let parts = Array::of1(&Uint8Array::from(bytes));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let window = web_sys::window().ok_or("there is no window")?;
+    let document = window.document().ok_or("there is no document")?;
+    let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+
+    // the download starts after this returns, so revoking the URL right away can cancel it
+    let revoke = Closure::once_into_js(move || {
+        if let Err(err) = Url::revoke_object_url(&url) {
+            log::warn!("failed to revoke {}: {:?}", url, err);
+        }
+    });
+    window.set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), REVOKE_DELAY_MS)?;
+
+    Ok(())
+}
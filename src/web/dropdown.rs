@@ -97,7 +97,9 @@ where P: PartialEq + Eq + Clone + fmt::Display + fmt::Debug + 'static
 
         let values_same = self.values.len() == new_props.values.len() && self.values.iter().zip(new_props.values.iter()).all(|(v1, v2)| v1.value == *v2);
         if !values_same {
-            let current_picked = self.picked().map(|v| &v.value);
+            // the value passed in props wins over the current pick, since the values may have changed to contain it
+            // (for example, when they are translated)
+            let current_picked = new_props.value.as_ref().or(self.picked().map(|v| &v.value));
             let new_values = Self::wrap_values(&new_props.values);
             let new_picked = current_picked.and_then(|picked_item| new_values.iter().enumerate().filter_map(|(idx, new_v)| if new_v.value == *picked_item {
                 Some(idx)
@@ -106,7 +108,10 @@ where P: PartialEq + Eq + Clone + fmt::Display + fmt::Debug + 'static
             }).next());
 
             self.values = new_values;
-            self.update_pick(new_picked);
+            if !self.update_pick(new_picked) {
+                // the pick stayed the same, but how it is displayed may have changed
+                self.user_input = self.picked().map(|v| v.display.clone());
+            }
             any_changes = true;
         }
 
@@ -165,7 +170,7 @@ impl<P> Dropdown<P> where P: PartialEq + Eq + Clone + fmt::Display + fmt::Debug
                     onfocus={link.callback(|_| DropdownMsg::InputFocus(true))}
                     onblur={link.callback(|_| DropdownMsg::InputFocus(false))}
                     {onkeyup}
-                    value={self.user_input.clone().unwrap_or(String::default())}
+                    value={self.user_input.clone().unwrap_or_default()}
                     disabled={self.disabled}
                 />
                 {
@@ -203,8 +208,8 @@ impl<P> Dropdown<P> where P: PartialEq + Eq + Clone + fmt::Display + fmt::Debug
         self.picked.and_then(|idx| self.values.get(idx))
     }
 
-    fn wrap_values(v: &Vec<P>) -> Vec<DropdownValue<P>> {
-        return v.iter()
+    fn wrap_values(v: &[P]) -> Vec<DropdownValue<P>> {
+        v.iter()
             .map(|v| DropdownValue {value: v.clone(), display: format!("{}", v)})
             .collect()
     }
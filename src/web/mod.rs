@@ -1,6 +1,9 @@
 mod app;
 mod dropdown;
+mod download;
 mod picker;
 mod solver;
 
-pub use app::App;
\ No newline at end of file
+pub use app::App;
+#[cfg(all(test, feature = "precomputed"))]
+pub(crate) use solver::{solver_start, SolverStart};
\ No newline at end of file
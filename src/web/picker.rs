@@ -1,13 +1,17 @@
 use yew::prelude::*;
-use crate::solver::{Month, TargetDate, Weekday};
+use crate::solver::{Locale, Localized, Month, Preset, TargetDate, Weekday};
 use super::dropdown::*;
 
 #[derive(PartialEq, Debug, Properties)]
 pub struct PickerProps {
+    pub preset: Preset,
+    pub locale: Locale,
     pub on_picked: Callback<Option<TargetDate>>,
 }
 
 pub struct Picker {
+    preset: Preset,
+    locale: Locale,
     leap_year: bool,
     month: Option<Month>,
     weekday: Option<Weekday>,
@@ -26,8 +30,10 @@ impl Component for Picker {
     type Message = PickerMsg;
     type Properties = PickerProps;
 
-    fn create(_: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         Self {
+            preset: ctx.props().preset,
+            locale: ctx.props().locale,
             leap_year: true,
             day: None,
             weekday: None,
@@ -73,7 +79,9 @@ impl Component for Picker {
                     if let Some(next_date) = target_date.next(self.leap_year) {
                         self.month = Some(next_date.month);
                         self.day = Some(next_date.day_of_month);
-                        self.weekday = Some(next_date.day_of_week);
+                        if self.uses_weekday() {
+                            self.weekday = Some(next_date.day_of_week);
+                        }
                         self.emit_selection(ctx);
                         return true;
                     }
@@ -84,26 +92,40 @@ impl Component for Picker {
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.locale = ctx.props().locale;
+        let new_preset = ctx.props().preset;
+        if self.preset != new_preset {
+            self.preset = new_preset;
+            if !self.uses_weekday() {
+                self.weekday = None;
+            }
+            self.emit_selection(ctx);
+        }
+
+        true
+    }
+
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
             <div class="target-picker">
-                <Dropdown<Weekday>
+                <Dropdown<Localized<Weekday>>
                     placeholder={"Weekday"}
-                    values={Self::weekday_values()}
-                    on_change={ctx.link().callback(|new_weekday| PickerMsg::PickWeekday(new_weekday))}
-                    value={self.weekday}
-                    disabled={false}/>
-                <Dropdown<Month>
+                    values={self.weekday_values()}
+                    on_change={ctx.link().callback(|weekday: Option<Localized<Weekday>>| PickerMsg::PickWeekday(weekday.map(|w| w.value)))}
+                    value={self.weekday.map(|weekday| Localized::new(weekday, self.locale))}
+                    disabled={!self.uses_weekday()}/>
+                <Dropdown<Localized<Month>>
                     placeholder={"Month"}
-                    values={Self::month_values()}
-                    on_change={ctx.link().callback(|new_month| PickerMsg::PickMonth(new_month))}
-                    value={self.month}
+                    values={self.month_values()}
+                    on_change={ctx.link().callback(|month: Option<Localized<Month>>| PickerMsg::PickMonth(month.map(|m| m.value)))}
+                    value={self.month.map(|month| Localized::new(month, self.locale))}
                     disabled={false}/>
                 <Dropdown<i8>
                     placeholder={"Day"}
                     values={self.day_of_month_values()}
-                    on_change={ctx.link().callback(|new_day| PickerMsg::PickDay(new_day))}
+                    on_change={ctx.link().callback(PickerMsg::PickDay)}
                     value={self.day}
                     disabled={self.month.is_none()}/>
                 <div class={classes!(
@@ -118,35 +140,27 @@ impl Component for Picker {
 
 impl Picker {
     fn target_date(&self) -> Option<TargetDate> {
-        self.month.zip(self.weekday).zip(self.day).map(|((month, day_of_week), day_of_month)| TargetDate{month, day_of_week, day_of_month})
+        // when the preset doesn't show the day of the week, the solver ignores it, so any weekday will do
+        let weekday = if self.uses_weekday() { self.weekday } else { Some(Weekday::Monday) };
+        self.month.zip(weekday).zip(self.day).map(|((month, day_of_week), day_of_month)| TargetDate{month, day_of_week, day_of_month})
+    }
+
+    fn uses_weekday(&self) -> bool {
+        self.preset.semantics().uses_weekday()
     }
 
-    fn month_values() -> Vec<Month> {
-        vec![
-            Month::January,
-            Month::February,
-            Month::March,
-            Month::April,
-            Month::May,
-            Month::June,
-            Month::July,
-            Month::August,
-            Month::September,
-            Month::November,
-            Month::December,
-        ]
+    fn month_values(&self) -> Vec<Localized<Month>> {
+        Month::ALL.iter().map(|month| Localized::new(*month, self.locale)).collect()
     }
 
-    fn weekday_values() -> Vec<Weekday> {
-        vec![
-            Weekday::Sunday,
-            Weekday::Monday,
-            Weekday::Tuesday,
-            Weekday::Wednesday,
-            Weekday::Thursday,
-            Weekday::Friday,
-            Weekday::Saturday,
-        ]
+    fn weekday_values(&self) -> Vec<Localized<Weekday>> {
+        // starting the week on Sunday, like most wall calendars
+        Weekday::ALL.iter()
+            .cycle()
+            .skip(6)
+            .take(7)
+            .map(|weekday| Localized::new(*weekday, self.locale))
+            .collect()
     }
 
     fn day_of_month_values(&self) -> Vec<i8> {
@@ -2,10 +2,16 @@ use web_sys::wasm_bindgen::closure::Closure;
 use web_sys::wasm_bindgen::JsCast;
 use yew::html::Scope;
 use yew::prelude::*;
-use crate::solver::{Month, Weekday, Solution, solve, SolverMsg, TaggedMask, TargetDate, PUZZLE_WIDTH, PUZZLE_HEIGHT, CellTag, BOARD_LABELS, BoardLabel};
+use crate::render::{GifRenderer, PngRenderer};
+use crate::solver::{Locale, Solution, solve_preset, solve_from, SolverMsg, TaggedMask, TargetDate, PUZZLE_WIDTH, PUZZLE_HEIGHT, CellTag, BoardLabel, Preset};
+#[cfg(feature = "precomputed")]
+use crate::solver::{lookup_solution, TABLE_PRESET};
+use super::download::download;
 
 #[derive(PartialEq, Debug, Properties)]
 pub struct SolverProps {
+    pub preset: Preset,
+    pub locale: Locale,
     pub target: Option<TargetDate>,
 }
 
@@ -15,11 +21,22 @@ pub enum SolverCmpMsg {
     ChangeSpeed(i32),
     TickSolver,
     FocusPiece(Option<usize>),
+    Animate,
+    DownloadImage,
+    DownloadAnimation,
 }
 
+// the number of solver steps taken on each tick, which the speed buttons halve and double within these bounds
+const DEFAULT_SPEED: i32 = 57;
+const MAX_SPEED: i32 = 57 << 6;
+
 pub struct SolverCmp {
+    preset: Preset,
+    locale: Locale,
     target: Option<TargetDate>,
     solver: Option<SolverState>,
+    // the solution for another date which the solver started from
+    previous: Option<Solution>,
     focus_piece: Option<usize>,
     speed: i32,
 }
@@ -34,6 +51,7 @@ struct SolvingState {
 enum SolverState {
     Solving(SolvingState),
     Solved(Solution),
+    Precomputed(Solution),
     Impossible(usize)
 }
 
@@ -43,14 +61,17 @@ impl Component for SolverCmp {
 
     fn create(ctx: &Context<Self>) -> Self {
         Self {
+            preset: ctx.props().preset,
+            locale: ctx.props().locale,
             target: ctx.props().target,
             solver: None,
-            speed: 57,
+            previous: None,
+            speed: DEFAULT_SPEED,
             focus_piece: None,
         }
     }
 
-    fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         log::debug!("got {:?}", msg);
         match msg {
             SolverCmpMsg::Reset => {
@@ -58,6 +79,7 @@ impl Component for SolverCmp {
             }
 
             SolverCmpMsg::ChangeSpeed(speed) => {
+                let speed = speed.clamp(1, MAX_SPEED);
                 if speed != self.speed {
                     self.speed = speed;
                     true
@@ -90,7 +112,7 @@ impl Component for SolverCmp {
 
                             true
                         },
-                        SolverState::Impossible(_) | SolverState::Solved(_) => {
+                        SolverState::Impossible(_) | SolverState::Solved(_) | SolverState::Precomputed(_) => {
                             false
                         }
                     }
@@ -103,22 +125,60 @@ impl Component for SolverCmp {
                 self.focus_piece = focus;
                 true
             }
+
+            SolverCmpMsg::Animate => {
+                if let Some(target) = self.target {
+                    self.start_solving(target, None, ctx.link());
+                    true
+                } else {
+                    false
+                }
+            }
+
+            SolverCmpMsg::DownloadImage => {
+                if let (Some(solution), Some(target)) = (self.solved(), self.target) {
+                    let renderer = PngRenderer { locale: self.locale, ..PngRenderer::new(self.preset) };
+                    if let Err(err) = download(&Self::file_name(target, "png"), "image/png", &renderer.render(&solution.mask)) {
+                        log::error!("failed to download the image: {:?}", err);
+                    }
+                }
+
+                false
+            }
+
+            SolverCmpMsg::DownloadAnimation => {
+                if let Some(target) = self.target {
+                    // this solves the date again (from the same start), since the steps aren't kept while solving
+                    let renderer = GifRenderer { locale: self.locale, ..GifRenderer::new(self.preset) };
+                    let gif = renderer.render(self.frames(target, self.previous.as_ref()));
+                    if let Some(Err(err)) = gif.map(|gif| download(&Self::file_name(target, "gif"), "image/gif", &gif)) {
+                        log::error!("failed to download the animation: {:?}", err);
+                    }
+                }
+
+                false
+            }
         }
     }
 
     fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
         let new_target = ctx.props().target;
-        if self.target != ctx.props().target {
+        let new_preset = ctx.props().preset;
+        let locale_changed = self.locale != ctx.props().locale;
+        self.locale = ctx.props().locale;
+        if self.target != new_target || self.preset != new_preset {
+            let last = self.target.zip(self.solved().copied());
             self.target = new_target;
+            self.preset = new_preset;
             if let Some(target) = self.target {
-                self.init_solver(target, ctx.link());
+                self.init_solver(target, last, ctx.link());
             } else {
                 self.take_solver();
             }
 
             true
         } else {
-            false
+            locale_changed
         }
     }
 
@@ -130,12 +190,60 @@ impl Component for SolverCmp {
                     {
                         match self.solver.as_ref() {
                             Some(SolverState::Solving(state)) => format!("solving... on step {}", state.steps),
-                            Some(SolverState::Solved(solution)) => format!("solved in {} steps", solution.steps),
+                            Some(SolverState::Solved(solution)) => match &self.previous {
+                                Some(previous) => format!("solved in {} steps, moving {} of the last solution's {} pieces",
+                                    solution.steps, solution.moved_pieces(previous), self.preset.num_pieces()),
+                                None => format!("solved in {} steps", solution.steps),
+                            },
+                            Some(SolverState::Precomputed(solution)) => format!("solved instantly (the solver takes {} steps)", solution.steps),
                             Some(SolverState::Impossible(steps)) => format!("impossible, determined in {} steps", steps),
+                            None if self.target.is_some() => "stopped".to_string(),
                             None => "select target date".to_string(),
                         }
                     }
                 </div>
+                {
+                    match self.solver {
+                        Some(SolverState::Solving(_)) => {
+                            let speed = self.speed;
+                            html! {
+                                <div class="speed">
+                                    <div class="speed-button button" onclick={ctx.link().callback(move |_| SolverCmpMsg::ChangeSpeed(speed / 2))}>
+                                        {"slower"}
+                                    </div>
+                                    <div class="speed-button button" onclick={ctx.link().callback(move |_| SolverCmpMsg::ChangeSpeed(speed * 2))}>
+                                        {"faster"}
+                                    </div>
+                                    <div class="speed-button button" onclick={ctx.link().callback(|_| SolverCmpMsg::Reset)}>
+                                        {"stop"}
+                                    </div>
+                                </div>
+                            }
+                        }
+                        Some(SolverState::Precomputed(_)) | None if self.target.is_some() => html! {
+                            <div class="animate-button button" onclick={ctx.link().callback(|_| SolverCmpMsg::Animate)}>
+                                {"watch the solver"}
+                            </div>
+                        },
+                        _ => html! { <></> }
+                    }
+                }
+                {
+                    match (self.solved(), self.target) {
+                        (Some(solution), Some(target)) => html! {
+                            <>
+                                <div class="share">{"share code: "}{solution.shared(target).to_string()}</div>
+                                <div class="download-button button" onclick={ctx.link().callback(|_| SolverCmpMsg::DownloadImage)}>
+                                    {"download image"}
+                                </div>
+                                <div class="download-button button" onclick={ctx.link().callback(|_| SolverCmpMsg::DownloadAnimation)}>
+                                    {"download animation"}
+                                </div>
+                            </>
+                        },
+                        _ => html! { <></> }
+                    }
+                }
                 <div class="tip">
                     {"tip: hover over any square to see what piece is covering it"}
                 </div>
@@ -147,8 +255,20 @@ impl Component for SolverCmp {
 
 impl SolverCmp {
 
-    fn init_solver(&mut self, target_date: TargetDate, link: &Scope<Self>) {
-        let mut frames = Box::new(solve(target_date));
+    fn init_solver(&mut self, target_date: TargetDate, last: Option<(TargetDate, Solution)>, link: &Scope<Self>) {
+        match solver_start(self.preset, target_date, last.as_ref().map(|(last_target, solution)| (*last_target, solution))) {
+            SolverStart::Precomputed(solution) => {
+                self.solver = Some(SolverState::Precomputed(solution));
+                self.previous = None;
+            }
+            SolverStart::Warm(previous) => self.start_solving(target_date, Some(previous), link),
+            SolverStart::Cold => self.start_solving(target_date, None, link),
+        }
+    }
+
+    fn start_solving(&mut self, target_date: TargetDate, previous: Option<Solution>, link: &Scope<Self>) {
+        let mut frames = self.frames(target_date, previous.as_ref());
+        self.previous = previous;
         self.solver = Some(match frames.next() {
             Some(SolverMsg::Unsolved(_, last_frame)) => SolverState::Solving(SolvingState {
                 frames,
@@ -156,17 +276,27 @@ impl SolverCmp {
                 _ticker: Ticker::create(100, link.callback(|_| SolverCmpMsg::TickSolver)),
                 steps: 0,
             }),
+            // a warm start can already be a solution, when the date didn't change
+            Some(SolverMsg::Solved(solution)) => SolverState::Solved(solution),
             Some(SolverMsg::Impossible) => SolverState::Impossible(0),
             v => panic!("unsupported initial state {:?}", v)
         });
     }
 
+    fn frames(&self, target_date: TargetDate, previous: Option<&Solution>) -> Box<dyn Iterator<Item=SolverMsg>> {
+        match previous {
+            Some(previous) => Box::new(solve_from(previous, target_date)),
+            None => Box::new(solve_preset(self.preset, target_date)),
+        }
+    }
+
     fn take_solver(&mut self) -> bool {
         self.solver.take().is_some()
     }
 
     fn view_board(&self, ctx: &Context<Self>) -> Html {
         let tagged_mask = self.tagged_mask();
+        let labels = self.preset.labels();
         html! {
             <div class="board">
                 {(0..PUZZLE_HEIGHT).map(move |y| html! {
@@ -175,13 +305,13 @@ impl SolverCmp {
                             <div
                                 class={classes!(
                                     "cell",
-                                    match BOARD_LABELS[y][x] {
+                                    match labels[y][x] {
                                         BoardLabel::MonthLabel(_) => "lbl-month",
                                         BoardLabel::DayLabel(_) => "lbl-day",
                                         BoardLabel::DayOfWeekLabel(_) => "lbl-weekday",
                                         BoardLabel::Unlabeled => "lbl-blank",
                                     },
-                                    tagged_mask.zip(self.focus_piece).and_then(|(tm, focus_piece_idx)| if tm.get(x, y) == CellTag::Covered(focus_piece_idx as u8) { Some("focus-light" )} else { Some("focus-dim") })
+                                    tagged_mask.zip(self.focus_piece).map(|(tm, focus_piece_idx)| if tm.get(x, y) == CellTag::Covered(focus_piece_idx as u8) { "focus-light" } else { "focus-dim" })
                                 )}
                                 onmouseenter={ctx.link().callback(move |_| SolverCmpMsg::FocusPiece(tagged_mask.and_then(|tm| if let CellTag::Covered(piece_idx) = tm.get(x, y) { Some(piece_idx as usize) } else { None })))}
                                 onmouseout={ctx.link().callback(|_| SolverCmpMsg::FocusPiece(None))}
@@ -199,33 +329,7 @@ impl SolverCmp {
                                 }
                                 <div class="lbl">
                                     {
-                                        match BOARD_LABELS[y][x] {
-                                            BoardLabel::MonthLabel(month) => match month {
-                                                Month::January => "JAN",
-                                                Month::February => "FEB",
-                                                Month::March => "MAR",
-                                                Month::April => "APR",
-                                                Month::May => "MAY",
-                                                Month::June => "JUN",
-                                                Month::July => "JUL",
-                                                Month::August => "AUG",
-                                                Month::September => "SEP",
-                                                Month::October => "OCT",
-                                                Month::November => "NOV",
-                                                Month::December => "DEC",
-                                            }.to_string(),
-                                            BoardLabel::DayLabel(day) => format!("{}", day),
-                                            BoardLabel::DayOfWeekLabel(weekday) => match weekday {
-                                                Weekday::Sunday => "SUN",
-                                                Weekday::Monday => "MON",
-                                                Weekday::Tuesday => "TUES",
-                                                Weekday::Wednesday => "WED",
-                                                Weekday::Thursday => "THUR",
-                                                Weekday::Friday => "FRI",
-                                                Weekday::Saturday => "SAT",
-                                            }.to_string(),
-                                            BoardLabel::Unlabeled => String::default(),
-                                        }
+                                        labels[y][x].text(self.locale)
                                     }
                                 </div>
                             </div>
@@ -236,10 +340,21 @@ impl SolverCmp {
         }
     }
 
+    fn file_name(target: TargetDate, extension: &str) -> String {
+        format!("calendar-{}-{}.{}", target.month.short_name(Locale::English).to_lowercase(), target.day_of_month, extension)
+    }
+
+    fn solved(&self) -> Option<&Solution> {
+        match &self.solver {
+            Some(SolverState::Solved(solution)) | Some(SolverState::Precomputed(solution)) => Some(solution),
+            _ => None,
+        }
+    }
+
     fn tagged_mask(&self) -> Option<TaggedMask> {
         if let Some(state) = &self.solver {
             Some(match state {
-                SolverState::Solved(solution) => solution.mask,
+                SolverState::Solved(solution) | SolverState::Precomputed(solution) => solution.mask,
                 SolverState::Solving(state) => state.last_frame,
                 _ => return None,
             })
@@ -249,6 +364,40 @@ impl SolverCmp {
     }
 }
 
+/// How the solver starts on a date.
+pub(crate) enum SolverStart {
+    /// The solution is looked up, and shown without running the solver.
+    Precomputed(Solution),
+    /// The solver moves the pieces of this solution, for the date before.
+    Warm(Solution),
+    /// The solver starts from an empty board.
+    Cold,
+}
+
+/// How the solver starts on `target_date`, after showing the solution for the `last` date.
+///
+/// Watching the solver move the pieces from the day before is the point of stepping through the dates with the ⪢
+/// button, so that starts from the last solution. Any other date is looked up in the precomputed table, when there is
+/// one for the preset.
+pub(crate) fn solver_start(preset: Preset, target_date: TargetDate, last: Option<(TargetDate, &Solution)>) -> SolverStart {
+    // the ⪢ button steps to the next day of a leap year, but stepping to March 1st after February 28th is the same
+    let is_next_day = |last_target: TargetDate| [true, false].into_iter().any(|leap_year| last_target.next(leap_year) == Some(target_date));
+    match last {
+        Some((last_target, solution)) if solution.game.preset() == preset && is_next_day(last_target) => SolverStart::Warm(*solution),
+        _ => precomputed_solution(preset, target_date).map_or(SolverStart::Cold, SolverStart::Precomputed),
+    }
+}
+
+#[cfg(feature = "precomputed")]
+fn precomputed_solution(preset: Preset, target_date: TargetDate) -> Option<Solution> {
+    (preset == TABLE_PRESET).then(|| lookup_solution(target_date)).flatten()
+}
+
+#[cfg(not(feature = "precomputed"))]
+fn precomputed_solution(_preset: Preset, _target_date: TargetDate) -> Option<Solution> {
+    None
+}
+
 struct Ticker {
     #[allow(unused)]
     _callback: Closure<dyn FnMut()>,
@@ -0,0 +1,26 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn cal_solve(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_cal-solve")).args(args).output().expect("failed to run cal-solve")
+}
+
+#[test]
+fn test_month_day_without_weekday() {
+    let output = cal_solve(&["--preset", "month-day", "--color", "never", "Oct", "17"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("*OCT*") && stdout.contains("*17*"), "{}", stdout);
+
+    // the classic board shows the weekday, so it's needed there
+    let output = cal_solve(&["Oct", "17"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_invalid_date_prints_one_line() {
+    let output = cal_solve(&["Sat", "Feb", "30"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8(output.stderr).unwrap().lines().count(), 1);
+}